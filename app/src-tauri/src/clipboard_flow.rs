@@ -4,7 +4,10 @@ use rdev::{Event, EventType, Key};
 use serde::{Deserialize, Serialize};
 use std::{cell::Cell, sync::Mutex};
 use tauri::{AppHandle, Emitter, Listener, LogicalPosition, Manager, WebviewWindow};
-use weapon::{Dps, DpsWithRunes, Weapon};
+use weapon::{
+    AffixAnalysis, BuildProfile, Dps, DpsWithRunes, DualWield, DualWieldRunes, PerfectWeapon,
+    Rarity, Tooltip, Weapon,
+};
 
 pub const WINDOW_LABEL: &str = "ClipboardFlowWindow";
 pub const WINDOW_TITLE: &str = "PoE2 Weapon";
@@ -12,18 +15,76 @@ pub const WINDOW_WIDTH: f64 = 400.;
 pub const WINDOW_HEIGHT: f64 = 600.;
 
 pub type State = Mutex<Option<Data>>;
+/// Weapon the copied one-handed weapons are paired with.
+pub type OffHandState = Mutex<Option<Weapon>>;
+/// Qualities the copied weapon is also evaluated at.
+pub type QualityTargetsState = Mutex<Vec<u8>>;
+
+pub const DEFAULT_QUALITY_TARGETS: [u8; 1] = [20];
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Data {
     pub weapon: WeaponWithCalculatedRunes,
     pub elapsed: u128,
     pub img: String,
-    pub weapon_q20: Option<WeaponWithCalculatedRunes>,
+    /// The weapon at configured quality targets above its quality.
+    pub at_quality: Vec<WeaponAtQuality>,
+    /// Best possible weapon of the same base and item level.
+    pub perfect: PerfectWeapon,
+    pub affixes: AffixAnalysis,
+    /// Socketed effects of the copied weapon match no known runes and are left out.
+    pub runes_unrecognized: bool,
+    /// Copied weapon dual wielded with the stored off-hand.
+    pub paired: Option<Paired>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Paired {
+    pub off_hand: Weapon,
+    pub dps: Dps,
+    pub best_runes: Option<DualWieldRunes>,
+}
+
+impl Paired {
+    pub fn new(weapon: &Weapon, off_hand: &Weapon) -> Option<Paired> {
+        let dual = DualWield::new(weapon.clone(), off_hand.clone()).ok()?;
+        Some(Paired {
+            dps: dual.dps(),
+            best_runes: dual
+                .with_different_runes(&BuildProfile::default())
+                .into_iter()
+                .next(),
+            off_hand: off_hand.clone(),
+        })
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponWithCalculatedRunes {
     pub weapon: Weapon,
     pub dps: Dps,
     pub dps_with_different_runes: Vec<DpsWithRunes>,
+    /// Displayed properties, predicted for what-if weapons.
+    pub tooltip: Tooltip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponAtQuality {
+    pub quality: u8,
+    /// None for quality only corruption can reach.
+    pub whetstones: Option<u8>,
+    pub weapon: WeaponWithCalculatedRunes,
+}
+
+impl WeaponAtQuality {
+    pub fn new(weapon: &Weapon, rarity: Rarity, targets: &[u8]) -> Vec<WeaponAtQuality> {
+        targets
+            .iter()
+            .filter(|quality| **quality > weapon.quality.0)
+            .map(|quality| WeaponAtQuality {
+                quality: *quality,
+                whetstones: weapon.whetstones_to(*quality, rarity),
+                weapon: WeaponWithCalculatedRunes::new(weapon.with_quality(*quality)),
+            })
+            .collect()
+    }
 }
 
 impl WeaponWithCalculatedRunes {
@@ -31,6 +92,7 @@ impl WeaponWithCalculatedRunes {
         let dps_with_different_runes = weapon.with_different_runes();
         let dps = weapon.dps();
         WeaponWithCalculatedRunes {
+            tooltip: weapon.tooltip(),
             weapon,
             dps,
             dps_with_different_runes,
@@ -163,20 +225,30 @@ fn blocking_get_updated_clipboard() -> Result<(String, u128), ClipboardError> {
 pub fn handle_ctrl_c(handle: &AppHandle) -> Result<(), Error> {
     let (contents, elapsed) = blocking_get_updated_clipboard().map_err(Error::Clipboard)?;
 
-    let weapon = parser::parse(&contents)
-        .map_err(Error::Parse)?
-        .into_weapon();
+    let parsed = parser::parse(&contents).map_err(Error::Parse)?;
+    let affixes = parsed.affix_analysis();
+    let runes_unrecognized = parsed.runes_unrecognized;
+    let weapon = parsed.into_weapon();
+    let paired = handle
+        .state::<OffHandState>()
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|off_hand| Paired::new(&weapon, off_hand));
+
+    let at_quality = WeaponAtQuality::new(
+        &weapon,
+        affixes.rarity,
+        &handle.state::<QualityTargetsState>().lock().unwrap(),
+    );
 
     let data = Data {
-        weapon_q20: match weapon.quality.0 == 20 {
-            true => None,
-            false => {
-                let mut weapon = weapon.clone();
-                weapon.quality.0 = 20;
-                Some(WeaponWithCalculatedRunes::new(weapon))
-            }
-        },
+        at_quality,
         img: weapon.base_stats().img.to_owned(),
+        perfect: weapon.perfect(),
+        affixes,
+        runes_unrecognized,
+        paired,
         weapon: WeaponWithCalculatedRunes::new(weapon),
         elapsed,
     };
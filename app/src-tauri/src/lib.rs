@@ -1,4 +1,4 @@
-use clipboard_flow::State;
+use clipboard_flow::{OffHandState, QualityTargetsState, State, DEFAULT_QUALITY_TARGETS};
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{
     menu::{Menu, MenuItem},
@@ -9,10 +9,35 @@ use tauri::{
 mod clipboard_flow;
 mod tray_window;
 mod commands {
+    use crate::clipboard_flow::{OffHandState, QualityTargetsState, State};
+
     #[tauri::command]
     pub async fn open_browser(url: String) {
         open::that(url).unwrap();
     }
+
+    /// Stores the last copied weapon as the off-hand to pair next copied weapons with.
+    #[tauri::command]
+    pub fn set_off_hand(
+        state: tauri::State<'_, State>,
+        off_hand: tauri::State<'_, OffHandState>,
+    ) -> bool {
+        let weapon = state
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|data| data.weapon.weapon.clone());
+        let stored = weapon.is_some();
+        *off_hand.lock().unwrap() = weapon;
+
+        stored
+    }
+
+    /// Qualities next copied weapons are evaluated at, besides their own.
+    #[tauri::command]
+    pub fn set_quality_targets(targets: Vec<u8>, state: tauri::State<'_, QualityTargetsState>) {
+        *state.lock().unwrap() = targets;
+    }
 }
 
 pub fn run() {
@@ -21,8 +46,14 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![commands::open_browser])
+        .invoke_handler(tauri::generate_handler![
+            commands::open_browser,
+            commands::set_off_hand,
+            commands::set_quality_targets
+        ])
         .manage::<State>(State::default())
+        .manage::<OffHandState>(OffHandState::default())
+        .manage::<QualityTargetsState>(QualityTargetsState::new(DEFAULT_QUALITY_TARGETS.to_vec()))
         .setup(|app| {
             add_tray(app);
             let handle = app.handle().clone();
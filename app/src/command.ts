@@ -2,6 +2,8 @@ import { invoke } from '@tauri-apps/api/core';
 
 export interface Commands {
 	open_browser: (args: { url: string }) => void;
+	set_off_hand: () => boolean;
+	set_quality_targets: (args: { targets: Array<number> }) => void;
 }
 
 const debug = false;
@@ -6,6 +6,16 @@ export const SUPPORTED_ITEM_CLASSES = [
 	'Crossbows',
 	'Spears',
 	'Talismans',
+	'One Hand Swords',
+	'Two Hand Swords',
+	'One Hand Axes',
+	'Two Hand Axes',
+	'Daggers',
+	'Claws',
+	'Flails',
+	'Wands',
+	'Staves',
+	'Sceptres',
 ] as const;
 
 export type ItemClass = (typeof SUPPORTED_ITEM_CLASSES)[number];
@@ -23,11 +33,29 @@ export const RUNES_VARIANTS = [
 	'LesserStorm',
 	'Storm',
 	'GreaterStorm',
+	'SoulCoreOfCitaqualotl',
+	'SoulCoreOfQuipolatl',
+	'SoulCoreOfHayoxi',
+	'SoulCoreOfTicaba',
 ] as const;
 export type Rune = (typeof RUNES_VARIANTS)[number];
-export const RUNE_TIERS = ['lesser', 'normal', 'greater'] as const;
+/** Rune tiers, Soul Cores are filtered as a tier of their own. */
+export const RUNE_TIERS = ['lesser', 'normal', 'greater', 'soul_core'] as const;
 export type RuneTier = (typeof RUNE_TIERS)[number];
 
+export function is_soul_core(rune: Rune): boolean {
+	return rune.startsWith('SoulCore');
+}
+
+/** Display name, e.g. "Greater Iron" or "Soul Core of Hayoxi". */
+export function rune_name(rune: Rune): string {
+	if (is_soul_core(rune)) {
+		return rune.replace('SoulCoreOf', 'Soul Core of ');
+	}
+
+	return rune.replace('Lesser', 'Lesser ').replace('Greater', 'Greater ');
+}
+
 export const DAMAGE_TYPES_VARIANTS = ['physical', 'fire', 'cold', 'lightning', 'chaos'];
 export type DamageType = (typeof DAMAGE_TYPES_VARIANTS)[number];
 
@@ -46,6 +74,8 @@ export type Weapon = {
 	atk_spd: number | null;
 	flats: Array<FlatDamage>;
 	runes: Array<Rune>;
+	sockets: number;
+	item_level: number | null;
 };
 
 export type Dps = {
@@ -53,10 +83,31 @@ export type Dps = {
 	pdps: number;
 	edps: number;
 	cdps: number;
+	fire: number;
+	cold: number;
+	lightning: number;
+	damages: Array<FlatDamage>;
+	crit_chance: number;
+	total_with_crit: number;
+	caster: CasterScore | null;
+};
+
+export type CasterScore = {
+	physical: number;
+	fire: number;
+	cold: number;
+	lightning: number;
+	chaos: number;
+	score: number;
 };
 
+/** Value weapons are ranked by: caster score for caster weapons, total DPS with crit otherwise. */
+export function dps_score(dps: Dps): number {
+	return dps.caster?.score ?? dps.total_with_crit;
+}
+
 export type DpsWithRunes = {
-	runes: [Rune] | [Rune, Rune];
+	runes: Array<Rune>;
 	dps: Dps;
 };
 
@@ -64,11 +115,74 @@ export type ClipboardFlowData = {
 	weapon: WeaponWithCalculatedRunes;
 	img: string;
 	elapsed: number;
-	weapon_q20?: WeaponWithCalculatedRunes;
+	at_quality: Array<WeaponAtQuality>;
+	perfect: PerfectWeapon;
+	affixes: AffixAnalysis;
+	/** Socketed effects match no known runes and are left out of DPS. */
+	runes_unrecognized: boolean;
+	paired: Paired | null;
+};
+
+export type Affix = 'prefix' | 'suffix';
+export type Rarity = 'normal' | 'magic' | 'rare' | 'unique';
+
+export type OpenSlot = {
+	affix: Affix;
+	mod_id: string;
+	name: string;
+	values: Array<number>;
+	dps: number;
+	dps_gain: number;
+};
+
+export type AffixAnalysis = {
+	rarity: Rarity;
+	prefixes: number;
+	suffixes: number;
+	unknown: number;
+	open_prefixes: number;
+	open_suffixes: number;
+	open_slots: Array<OpenSlot>;
+	reliable: boolean;
+};
+
+export type PerfectWeapon = {
+	weapon: Weapon;
+	dps: Dps;
+};
+
+export type DualWieldRunes = {
+	main_hand: Array<Rune>;
+	off_hand: Array<Rune>;
+	dps: Dps;
+};
+
+/** Copied weapon dual wielded with the stored off-hand. */
+export type Paired = {
+	off_hand: Weapon;
+	dps: Dps;
+	best_runes: DualWieldRunes | null;
+};
+
+export type WeaponAtQuality = {
+	quality: number;
+	/** Null for quality only corruption can reach. */
+	whetstones: number | null;
+	weapon: WeaponWithCalculatedRunes;
 };
 
 export type WeaponWithCalculatedRunes = {
 	weapon: Weapon;
 	dps: Dps;
 	dps_with_different_runes: Array<DpsWithRunes>;
+	/** Displayed properties, predicted for what-if weapons. */
+	tooltip: Tooltip;
+};
+
+export type Tooltip = {
+	quality: number;
+	damages: Array<FlatDamage>;
+	crit_chance: number;
+	attacks_per_second: number;
+	lines: Array<string>;
 };
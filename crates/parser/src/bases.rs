@@ -1,4 +1,4 @@
-pub const BASES: [&str; 148] = [
+pub const BASES: [&str; 191] = [
 	"Wooden Club",
 	"Smithing Hammer",
 	"Slim Mace",
@@ -147,4 +147,47 @@ pub const BASES: [&str; 148] = [
 	"Fungal Talisman",
 	"Jade Talisman",
 	"Maji Talisman",
+	"Shortsword",
+	"Broadsword",
+	"Vaal Blade",
+	"Corroded Longsword",
+	"Iron Greatsword",
+	"Keyblade",
+	"Dull Hatchet",
+	"Hook Axe",
+	"Bearded Axe",
+	"Splitting Greataxe",
+	"Arched Greataxe",
+	"Executioner Greataxe",
+	"Glass Shank",
+	"Skinning Knife",
+	"Stiletto",
+	"Twin Claw",
+	"Gemini Claw",
+	"Vaal Claw",
+	"Splintered Flail",
+	"Chain Flail",
+	"Holy Flail",
+	"Withered Wand",
+	"Bone Wand",
+	"Attuned Wand",
+	"Siphoning Wand",
+	"Volatile Wand",
+	"Galvanic Wand",
+	"Acrid Wand",
+	"Offering Wand",
+	"Ashen Staff",
+	"Gelid Staff",
+	"Voltaic Staff",
+	"Spriggan Staff",
+	"Pyrophyte Staff",
+	"Chiming Staff",
+	"Reaping Staff",
+	"Roaring Staff",
+	"Rattling Sceptre",
+	"Stoic Sceptre",
+	"Lupine Sceptre",
+	"Omen Sceptre",
+	"Shrine Sceptre",
+	"Ochre Sceptre",
 ];
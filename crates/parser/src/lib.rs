@@ -6,11 +6,14 @@ use std::str::FromStr;
 use bases::BASES;
 use serde::{Deserialize, Serialize};
 use weapon::{
-    AttackSpeedModifier, DamageType, Explicits, FlatDamage, ItemClass, PhysModifier, Quality,
-    Range, Rune, RuneMartialBonus, Weapon, WEAPON_STATS,
+    Affix, AffixAnalysis, AttackSpeedModifier, CastSpeedModifier, CasterExplicits, CraftItem,
+    CritChanceModifier, CritDamageModifier, DamageType, ElementalDamageModifier, Explicits,
+    FlatDamage, IncreasedDamage, ItemClass, ModStats, PhysModifier, Quality, Range, Rarity, Rune,
+    RuneCasterBonus, RuneMartialBonus, SocketedBonus, SpellDamageModifier, SpellLevels, Weapon,
+    SECTION_SEPARATOR, WEAPON_STATS,
 };
 
-pub const SUPPORTED_ITEM_CLASSES: [&str; 7] = [
+pub const SUPPORTED_ITEM_CLASSES: [&str; 17] = [
     "One Hand Maces",
     "Two Hand Maces",
     "Quarterstaves",
@@ -18,6 +21,16 @@ pub const SUPPORTED_ITEM_CLASSES: [&str; 7] = [
     "Crossbows",
     "Spears",
     "Talismans",
+    "One Hand Swords",
+    "Two Hand Swords",
+    "One Hand Axes",
+    "Two Hand Axes",
+    "Daggers",
+    "Claws",
+    "Flails",
+    "Wands",
+    "Staves",
+    "Sceptres",
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -25,25 +38,79 @@ pub struct Parsed {
     pub base: String,
     pub item_class: ItemClass,
     pub explicits: Explicits,
+    pub implicits: Explicits,
+    pub enchants: Explicits,
+    /// Most probable socketed runes. See [`Parsed::rune_candidates`].
     pub runes: Vec<Rune>,
+    /// Every rune combination consistent with socketed effects of the item.
+    pub rune_candidates: Vec<Vec<Rune>>,
+    /// More than one rune combination gives the same socketed effects,
+    /// e.g. 32% increased Physical Damage is two Iron runes or Lesser and Greater Iron.
+    pub runes_ambiguous: bool,
+    /// Socketed effects of the item are not those of any known rune combination,
+    /// so they are left out of [`Parsed::runes`] and the DPS.
+    pub runes_unrecognized: bool,
     pub quality: Quality,
+    /// Number of sockets from "Sockets: S S" line, if present.
+    pub sockets: Option<u8>,
+    pub item_level: Option<u8>,
+    pub rarity: Option<Rarity>,
+    /// Explicit lines recognized as prefixes. A hybrid modifier is one entry
+    /// with its lines joined by a newline.
+    pub prefixes: Vec<String>,
+    /// Explicit lines recognized as suffixes.
+    pub suffixes: Vec<String>,
+    /// Explicit lines not in mods.json. Each takes a prefix or a suffix slot.
+    pub unknown_explicits: Vec<String>,
+    /// Increased Physical Damage and Accuracy lines may be one hybrid prefix or two prefixes.
+    /// They are counted as one.
+    pub hybrid_ambiguous: bool,
 }
 
 impl Parsed {
     pub fn into_weapon(self) -> Weapon {
         self.into()
     }
+
+    /// Item state for crafting. Items without rarity line are taken as rare.
+    pub fn craft_item(&self) -> CraftItem {
+        let item = CraftItem::new(
+            self.clone().into_weapon(),
+            self.rarity.unwrap_or(Rarity::Rare),
+        );
+        let other_prefixes =
+            (self.prefixes.len() as u8).saturating_sub(item.affixes(Affix::Prefix));
+        let other_suffixes =
+            (self.suffixes.len() as u8).saturating_sub(item.affixes(Affix::Suffix));
+        item.with_other_mods(other_prefixes, other_suffixes)
+            .with_unknown_mods(self.unknown_explicits.len() as u8)
+    }
+
+    /// Open affixes of the item. Not reliable with unknown or possibly hybrid explicits.
+    pub fn affix_analysis(&self) -> AffixAnalysis {
+        let mut analysis = self.craft_item().affix_analysis();
+        analysis.reliable &= !self.hybrid_ambiguous;
+        analysis
+    }
 }
 
 impl From<Parsed> for Weapon {
     fn from(value: Parsed) -> Self {
-        Weapon {
+        let mut weapon = Weapon {
             base: value.base,
             item_class: value.item_class,
             quality: value.quality,
             explicits: value.explicits,
+            implicits: value.implicits,
+            enchants: value.enchants,
             runes: value.runes,
-        }
+            sockets: 0,
+            item_level: value.item_level,
+        };
+        weapon.sockets = value
+            .sockets
+            .unwrap_or_else(|| weapon.handed().default_sockets());
+        weapon
     }
 }
 
@@ -63,9 +130,6 @@ pub fn parse(text: &str) -> Result<Parsed, ParseError> {
 
     let mut base: Option<String> = None;
     let mut quality = Quality::default();
-    let mut phys: Option<PhysModifier> = None;
-    let mut atk_spd: Option<AttackSpeedModifier> = None;
-    let mut flats: Vec<FlatDamage> = vec![];
 
     let mut item_level_line_met = false;
     let mut lines = text.lines();
@@ -112,62 +176,113 @@ pub fn parse(text: &str) -> Result<Parsed, ParseError> {
     }
 
     let item_class = serde_json::from_str::<ItemClass>(&format!("\"{item_class}\"")).unwrap();
+    let sockets = text.lines().find_map(try_parse_sockets);
+    let item_level = text.lines().find_map(try_parse_item_level);
+    let rarity = text.lines().find_map(try_parse_rarity);
 
-    let mut runes: Vec<Rune> = vec![];
+    let mut socketed: Vec<SocketedBonus> = vec![];
+    let mut unknown_socketed = false;
 
-    if !item_level_line_met {
-        for line in lines.skip_while(|s| !s.starts_with("Item Level")) {
-            if atk_spd.is_some() {
-                break;
-            }
+    let explicit_lines: Vec<&str> = match item_level_line_met {
+        true => lines.collect(),
+        false => lines.skip_while(|s| !s.starts_with("Item Level")).collect(),
+    };
 
-            if phys.is_none() {
-                // Runes come right before explicits. Phys is our first explicit.
-                if let Some(line_runes) = try_parse_rune(line) {
-                    runes.extend(line_runes);
-                    continue;
-                }
-
-                if let Some(p) = try_parse_phys_modifier(line) {
-                    phys = Some(p);
-                    continue;
-                }
-            }
+    let mut explicits = Explicits {
+        caster: item_class.is_caster().then(CasterExplicits::default),
+        ..Default::default()
+    };
+    let mut implicits = explicits.clone();
+    let mut enchants = explicits.clone();
+    let mut implicit_lines_met = false;
+    let mut mod_lines: Vec<&str> = vec![];
+
+    for line in explicit_lines {
+        // Damage explicits end with attack speed. Lines after it are only classified.
+        if explicits.atk_spd.is_some() {
+            mod_lines.push(line);
+            continue;
+        }
 
-            if let Some(flat) = try_parse_flat_damage(line) {
-                flats.push(flat);
-                continue;
-            }
+        if let Some(implicit) = line.strip_suffix("(implicit)") {
+            implicit_lines_met = true;
+            try_parse_mod_line(implicit.trim(), &mut implicits);
+            continue;
+        }
 
-            if let Some(aspd) = try_parse_attack_speed_modifier(line) {
-                atk_spd = Some(aspd)
-            }
+        if let Some(enchant) = line.strip_suffix("(enchant)") {
+            try_parse_mod_line(enchant.trim(), &mut enchants);
+            continue;
         }
-    } else {
-        for line in lines {
-            if atk_spd.is_some() {
-                break;
-            }
-            if phys.is_none() {
-                // Runes come right before explicits. Phys is our first explicit.
-                if let Some(line_runes) = try_parse_rune(line) {
-                    runes.extend(line_runes);
-                    continue;
-                }
-
-                if let Some(p) = try_parse_phys_modifier(line) {
-                    phys = Some(p);
-                    continue;
-                }
-            }
 
-            if let Some(flat) = try_parse_flat_damage(line) {
-                flats.push(flat);
-                continue;
+        if line.ends_with("(rune)") {
+            match try_parse_socketed_bonus(line, item_class.is_caster()) {
+                Some(bonus) => socketed.push(bonus),
+                None => unknown_socketed = true,
             }
+            continue;
+        }
+
+        mod_lines.push(line);
+        try_parse_mod_line(line, &mut explicits);
+    }
+
+    // Explicits are the last section with a known modifier. Sections after it are
+    // e.g. "Corrupted" or flavour text.
+    let explicit_section = mod_lines
+        .rsplit(|line| *line == SECTION_SEPARATOR)
+        .find(|section| {
+            section
+                .iter()
+                .any(|line| ModStats::from_line(line, item_class).is_some())
+        })
+        .unwrap_or_default();
+
+    let mut prefixes = vec![];
+    let mut suffixes = vec![];
+    let mut unknown_explicits = vec![];
+    for line in explicit_section {
+        // Trade site texts have no separator before requirements.
+        if line.is_empty() || line.starts_with("Requires ") {
+            continue;
+        }
+        match ModStats::from_line(line, item_class).map(|stats| stats.affix) {
+            Some(Affix::Prefix) => prefixes.push(line.to_string()),
+            Some(Affix::Suffix) => suffixes.push(line.to_string()),
+            None => unknown_explicits.push(line.to_string()),
+        }
+    }
 
-            if let Some(aspd) = try_parse_attack_speed_modifier(line) {
-                atk_spd = Some(aspd)
+    // Physical Damage and Accuracy of one hybrid prefix are shown as two lines.
+    let is_phys = |line: &String| try_parse_phys_modifier(line).is_some();
+    let accuracy = prefixes
+        .iter()
+        .position(|line| line.ends_with(" to Accuracy Rating"));
+    let mut hybrid_ambiguous = false;
+    if let (true, Some(accuracy)) = (prefixes.iter().any(is_phys), accuracy) {
+        let max_prefixes = rarity.unwrap_or(Rarity::Rare).max_affixes() as usize;
+        hybrid_ambiguous = prefixes.len() <= max_prefixes;
+        let accuracy = prefixes.remove(accuracy);
+        let phys = prefixes.iter_mut().find(|line| is_phys(line)).unwrap();
+        phys.push('\n');
+        phys.push_str(&accuracy);
+    }
+
+    // Socketed effects of the same stat are summed up on the item. Find every rune combination,
+    // which could produce them.
+    let rune_candidates = Rune::decompose(
+        &socketed,
+        sockets.unwrap_or_else(|| item_class.handed().default_sockets()),
+        item_class.is_caster(),
+    );
+    let runes = rune_candidates.first().cloned().unwrap_or_default();
+    let runes_unrecognized = unknown_socketed || (!socketed.is_empty() && runes.is_empty());
+
+    // Trade site texts may miss implicits. Take them from base data.
+    if !implicit_lines_met {
+        if let Some(stats) = WEAPON_STATS.iter().find(|s| s.base == base) {
+            for line in &stats.implicits {
+                try_parse_mod_line(&average_ranges(line), &mut implicits);
             }
         }
     }
@@ -175,112 +290,86 @@ pub fn parse(text: &str) -> Result<Parsed, ParseError> {
     Ok(Parsed {
         base,
         item_class,
-        explicits: Explicits {
-            flats,
-            phys,
-            atk_spd,
-        },
+        explicits,
+        implicits,
+        enchants,
         runes,
+        runes_ambiguous: rune_candidates.len() > 1,
+        runes_unrecognized,
+        rune_candidates,
         quality,
+        sockets,
+        item_level,
+        rarity,
+        prefixes,
+        suffixes,
+        unknown_explicits,
+        hybrid_ambiguous,
     })
 }
 
-fn try_parse_rune(line: &str) -> Option<Vec<Rune>> {
-    let original_line = line.trim();
-    if !original_line.ends_with("(rune)") {
-        return None;
+/// Replaces roll ranges of base data lines with their average,
+/// e.g. "(40-60)% increased Flammability Magnitude" with "50% increased Flammability Magnitude".
+fn average_ranges(line: &str) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('(') {
+        let Some(end) = rest[start..].find(')').map(|end| start + end) else {
+            break;
+        };
+        let average = rest[start + 1..end].split_once('-').and_then(|(min, max)| {
+            Some((min.parse::<f32>().ok()? + max.parse::<f32>().ok()?) / 2.0)
+        });
+
+        result.push_str(&rest[..start]);
+        match average {
+            Some(average) => result.push_str(&average.round().to_string()),
+            None => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
     }
-    let mod_line = original_line.replace("(rune)", "").trim().to_string();
+    result.push_str(rest);
 
-    // Attempt to parse as Physical Damage Modifier
-    if let Some(parsed_phys_mod) = try_parse_phys_modifier(&mod_line) {
-        let phys_val = parsed_phys_mod.0;
+    result
+}
 
-        let greater_iron_val = Rune::greater_iron_rune_martial().0;
-        let iron_val = Rune::iron_rune_martial().0;
-        let lesser_iron_val = Rune::lesser_iron_rune_martial().0;
+/// Socketed effect of rune line, e.g. "32% increased Physical Damage (rune)".
+fn try_parse_socketed_bonus(line: &str, caster: bool) -> Option<SocketedBonus> {
+    let mod_line = line.trim().strip_suffix("(rune)")?.trim();
 
-        if greater_iron_val > 0 && phys_val % greater_iron_val == 0 {
-            let count = (phys_val / greater_iron_val) as usize;
-            if count > 0 {
-                return Some(vec![Rune::GreaterIron; count]);
-            }
-        }
-        if iron_val > 0 && phys_val % iron_val == 0 {
-            let count = (phys_val / iron_val) as usize;
-            if count > 0 {
-                return Some(vec![Rune::Iron; count]);
-            }
-        }
-        if lesser_iron_val > 0 && phys_val % lesser_iron_val == 0 {
-            let count = (phys_val / lesser_iron_val) as usize;
-            if count > 0 {
-                return Some(vec![Rune::LesserIron; count]);
-            }
-        }
+    if caster {
+        return try_parse_rune_caster_bonus(mod_line).map(SocketedBonus::Caster);
+    }
 
-        return None;
+    if let Some(phys) = try_parse_phys_modifier(mod_line) {
+        return Some(SocketedBonus::Martial(RuneMartialBonus::Phys(phys)));
+    }
 
-    // Attempt to parse as Flat Damage Modifier
-    } else if let Some(parsed_flat_mod) = try_parse_flat_damage(&mod_line) {
-        // Define the base rune tiers and their corresponding enum variants.
-        // Order might matter if there's ambiguity, typically checking larger base runes first for multiples.
-        let candidate_rune_tiers = [
-            (Rune::greater_desert_rune_martial(), Rune::GreaterDesert),
-            (Rune::desert_rune_martial(), Rune::Desert),
-            (Rune::lesser_desert_rune_martial(), Rune::LesserDesert),
-            (Rune::greater_glacial_rune_martial(), Rune::GreaterGlacial),
-            (Rune::glacial_rune_martial(), Rune::Glacial),
-            (Rune::lesser_glacial_rune_martial(), Rune::LesserGlacial),
-            (Rune::greater_storm_rune_martial(), Rune::GreaterStorm),
-            (Rune::storm_rune_martial(), Rune::Storm),
-            (Rune::lesser_storm_rune_martial(), Rune::LesserStorm),
-        ];
-
-        // Stage 1: Try for an exact single rune match
-        for (tier_stats, rune_variant) in &candidate_rune_tiers {
-            if tier_stats.damage_type == parsed_flat_mod.damage_type
-                && tier_stats.range == parsed_flat_mod.range
-            {
-                return Some(vec![*rune_variant]); // Exact match for one rune
-            }
-        }
+    if let Some(flat) = try_parse_flat_damage(mod_line) {
+        return Some(SocketedBonus::Martial(RuneMartialBonus::Flat(flat)));
+    }
 
-        // Stage 2: If no exact single rune match, try to parse as multiples of a base rune tier
-        // We iterate in the same order (Greater, Normal, Lesser for each element type)
-        // to give precedence to multiples of larger runes if ambiguity were possible.
-        for (tier_stats, rune_variant) in &candidate_rune_tiers {
-            if tier_stats.damage_type == parsed_flat_mod.damage_type {
-                let tier_min = tier_stats.range.0;
-                let tier_max = tier_stats.range.1;
-                let parsed_min = parsed_flat_mod.range.0;
-                let parsed_max = parsed_flat_mod.range.1;
-
-                // Ensure the base tier rune has valid damage values to avoid division by zero or logical errors.
-                if tier_min > 0 && tier_max > 0 {
-                    // Min damage typically > 0 for elemental runes
-                    if parsed_min % tier_min == 0 && parsed_max % tier_max == 0 {
-                        let count_from_min = parsed_min / tier_min;
-                        let count_from_max = parsed_max / tier_max;
-
-                        // Both min and max damage must scale by the same factor, and that factor must be greater than 0.
-                        if count_from_min == count_from_max && count_from_min > 0 {
-                            // If count is 1, it should have been caught by the exact match (Stage 1).
-                            // This block is for actual multiples (count > 1).
-                            if count_from_min > 0 {
-                                // count_from_min == 1 should be caught by Stage 1
-                                return Some(vec![*rune_variant; count_from_min as usize]);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    try_parse_socketable_bonus(mod_line).map(SocketedBonus::Martial)
+}
+
+fn try_parse_socketable_bonus(line: &str) -> Option<RuneMartialBonus> {
+    if let Some(atk_spd) = try_parse_attack_speed_modifier(line) {
+        return Some(RuneMartialBonus::AttackSpeed(atk_spd));
+    }
 
-        return None; // No matching elemental rune tier or multiple found
+    if let Some(crit_chance) = try_parse_crit_chance_modifier(line) {
+        return Some(RuneMartialBonus::CritChance(crit_chance));
     }
 
-    None // Line is not a recognized rune modifier
+    if let Some(crit_dmg) = try_parse_crit_damage_modifier(line) {
+        return Some(RuneMartialBonus::CritDamage(crit_dmg));
+    }
+
+    if let Some(elemental) = try_parse_elemental_attack_modifier(line) {
+        return Some(RuneMartialBonus::IncreasedElemental(elemental));
+    }
+
+    None
 }
 
 /// Try find Adds 7 to 16 Fire Damage
@@ -368,6 +457,186 @@ fn try_parse_attack_speed_modifier(line: &str) -> Option<AttackSpeedModifier> {
     None
 }
 
+fn try_parse_rune_caster_bonus(line: &str) -> Option<RuneCasterBonus> {
+    if let Some(spell_damage) = try_parse_spell_damage_modifier(line) {
+        return Some(RuneCasterBonus::SpellDamage(spell_damage));
+    }
+
+    if let Some(increased) = try_parse_increased_damage(line) {
+        return Some(RuneCasterBonus::Increased(increased));
+    }
+
+    if let Some(cast_speed) = try_parse_cast_speed_modifier(line) {
+        return Some(RuneCasterBonus::CastSpeed(cast_speed));
+    }
+
+    None
+}
+
+/// Fills modifiers from the line. Returns true, if the line is a recognized modifier.
+fn try_parse_mod_line(line: &str, modifiers: &mut Explicits) -> bool {
+    // Caster weapons have no local damage, e.g. increased Physical Damage is for spells.
+    if let Some(caster) = &mut modifiers.caster {
+        if try_parse_caster_line(line, caster) {
+            return true;
+        }
+    }
+
+    if modifiers.phys.is_none() {
+        if let Some(p) = try_parse_phys_modifier(line) {
+            modifiers.phys = Some(p);
+            return true;
+        }
+    }
+
+    if let Some(flat) = try_parse_flat_damage(line) {
+        modifiers.flats.push(flat);
+        return true;
+    }
+
+    if let Some(c) = try_parse_crit_chance_modifier(line) {
+        modifiers.crit_chance = Some(c);
+        return true;
+    }
+
+    if let Some(c) = try_parse_crit_damage_modifier(line) {
+        modifiers.crit_dmg = Some(c);
+        return true;
+    }
+
+    if let Some(aspd) = try_parse_attack_speed_modifier(line) {
+        modifiers.atk_spd = Some(aspd);
+        return true;
+    }
+
+    false
+}
+
+/// Fills caster explicits from the line. Returns true, if the line is a caster modifier.
+fn try_parse_caster_line(line: &str, caster: &mut CasterExplicits) -> bool {
+    if let Some(levels) = try_parse_spell_levels(line) {
+        caster.spell_levels = Some(levels);
+        return true;
+    }
+
+    if let Some(spell_damage) = try_parse_spell_damage_modifier(line) {
+        caster.spell_damage = Some(spell_damage);
+        return true;
+    }
+
+    if let Some(increased) = try_parse_increased_damage(line) {
+        caster.increased.push(increased);
+        return true;
+    }
+
+    if let Some(cast_speed) = try_parse_cast_speed_modifier(line) {
+        caster.cast_speed = Some(cast_speed);
+        return true;
+    }
+
+    false
+}
+
+/// +2 to Level of all Spell Skills
+fn try_parse_spell_levels(line: &str) -> Option<SpellLevels> {
+    let value = line
+        .strip_prefix("+")?
+        .strip_suffix(" to Level of all Spell Skills")?
+        .parse::<u8>()
+        .ok()?;
+
+    Some(SpellLevels(value))
+}
+
+/// 69% increased Spell Damage
+fn try_parse_spell_damage_modifier(line: &str) -> Option<SpellDamageModifier> {
+    let value = line
+        .strip_suffix("% increased Spell Damage")?
+        .parse::<u16>()
+        .ok()?;
+
+    Some(SpellDamageModifier(value))
+}
+
+/// 45% increased Fire Damage
+fn try_parse_increased_damage(line: &str) -> Option<IncreasedDamage> {
+    let (value, rest) = line.split_once("% increased ")?;
+    let damage_type = match rest {
+        "Physical Damage" => DamageType::Physical,
+        "Fire Damage" => DamageType::Fire,
+        "Cold Damage" => DamageType::Cold,
+        "Lightning Damage" => DamageType::Lightning,
+        "Chaos Damage" => DamageType::Chaos,
+        _ => return None,
+    };
+
+    Some(IncreasedDamage {
+        damage_type,
+        value: value.parse::<u16>().ok()?,
+    })
+}
+
+/// 18% increased Cast Speed
+fn try_parse_cast_speed_modifier(line: &str) -> Option<CastSpeedModifier> {
+    let value = line
+        .strip_suffix("% increased Cast Speed")?
+        .parse::<u8>()
+        .ok()?;
+
+    Some(CastSpeedModifier(value))
+}
+
+/// 30% increased Elemental Damage with Attacks
+fn try_parse_elemental_attack_modifier(line: &str) -> Option<ElementalDamageModifier> {
+    let value = line
+        .strip_suffix("% increased Elemental Damage with Attacks")?
+        .parse::<u16>()
+        .ok()?;
+
+    Some(ElementalDamageModifier(value))
+}
+
+/// +1.5% to Critical Hit Chance
+fn try_parse_crit_chance_modifier(line: &str) -> Option<CritChanceModifier> {
+    let value = line
+        .strip_prefix("+")?
+        .strip_suffix("% to Critical Hit Chance")?
+        .parse::<f32>()
+        .ok()?;
+
+    Some(CritChanceModifier(value))
+}
+
+/// +16% to Critical Damage Bonus
+fn try_parse_crit_damage_modifier(line: &str) -> Option<CritDamageModifier> {
+    let value = line
+        .strip_prefix("+")?
+        .strip_suffix("% to Critical Damage Bonus")?
+        .parse::<u16>()
+        .ok()?;
+
+    Some(CritDamageModifier(value))
+}
+
+/// Sockets: S S
+fn try_parse_sockets(line: &str) -> Option<u8> {
+    let sockets = line.strip_prefix("Sockets:")?;
+    Some(sockets.split_whitespace().filter(|s| *s == "S").count() as u8)
+}
+
+fn try_parse_rarity(line: &str) -> Option<Rarity> {
+    let rarity = line.strip_prefix("Rarity:")?.trim().to_lowercase();
+    serde_json::from_value(serde_json::Value::String(rarity)).ok()
+}
+
+fn try_parse_item_level(line: &str) -> Option<u8> {
+    let level = line.strip_prefix("Item Level:")?.trim();
+    let digits = level
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(level.len());
+    level[..digits].parse().ok()
+}
+
 fn try_parse_quality(line: &str) -> Option<Quality> {
     let mut iter = line.split(" ");
     if iter.next() != Some("Quality:") {
@@ -386,7 +655,10 @@ fn try_parse_quality(line: &str) -> Option<Quality> {
 
 #[cfg(test)]
 mod tests {
-    use weapon::{AttackSpeedModifier, DamageType, FlatDamage, PhysModifier, Quality, Range, Rune};
+    use weapon::{
+        AttackSpeedModifier, CritChanceModifier, CritDamageModifier, DamageType, FlatDamage,
+        PhysModifier, Quality, Range, Rarity, Rune, RuneMartialBonus, SocketedBonus,
+    };
 
     #[test]
     fn try_parse_flat_damage() {
@@ -415,6 +687,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn try_parse_sockets() {
+        assert_eq!(Some(3), super::try_parse_sockets("Sockets: S S S "));
+        assert_eq!(None, super::try_parse_sockets("Item Level: 75"));
+    }
+
+    #[test]
+    fn try_parse_rarity() {
+        assert_eq!(
+            Some(Rarity::Magic),
+            super::try_parse_rarity("Rarity: Magic")
+        );
+        assert_eq!(None, super::try_parse_rarity("Item Class: Bows"));
+    }
+
+    #[test]
+    fn try_parse_item_level() {
+        assert_eq!(Some(75), super::try_parse_item_level("Item Level: 75"));
+        assert_eq!(
+            Some(29),
+            super::try_parse_item_level("Item Level: 29ffdps:85.4")
+        );
+    }
+
     #[test]
     fn try_parse_attack_speed_modifier() {
         assert_eq!(
@@ -424,10 +720,50 @@ mod tests {
     }
 
     #[test]
-    fn try_parse_rune() {
+    fn try_parse_crit_modifiers() {
         assert_eq!(
-            Some(vec![Rune::Iron, Rune::Iron]),
-            super::try_parse_rune("32% increased Physical Damage (rune)")
-        )
+            Some(CritChanceModifier(1.51)),
+            super::try_parse_crit_chance_modifier("+1.51% to Critical Hit Chance")
+        );
+        assert_eq!(
+            Some(CritDamageModifier(16)),
+            super::try_parse_crit_damage_modifier("+16% to Critical Damage Bonus")
+        );
+    }
+
+    #[test]
+    fn average_ranges() {
+        assert_eq!(
+            "50% increased Flammability Magnitude",
+            super::average_ranges("(40-60)% increased Flammability Magnitude")
+        );
+        assert_eq!(
+            "Adds 4 to 9 Fire Damage",
+            super::average_ranges("Adds (3-5) to (8-10) Fire Damage")
+        );
+        assert_eq!(
+            "Loads an additional bolt",
+            super::average_ranges("Loads an additional bolt")
+        );
+    }
+
+    #[test]
+    fn try_parse_socketed_bonus() {
+        assert_eq!(
+            Some(SocketedBonus::Martial(RuneMartialBonus::Phys(
+                PhysModifier(32)
+            ))),
+            super::try_parse_socketed_bonus("32% increased Physical Damage (rune)", false)
+        );
+        assert_eq!(
+            Some(SocketedBonus::Martial(RuneMartialBonus::CritDamage(
+                CritDamageModifier(12)
+            ))),
+            super::try_parse_socketed_bonus("+12% to Critical Damage Bonus (rune)", false)
+        );
+        assert_eq!(
+            None,
+            super::try_parse_socketed_bonus("32% increased Physical Damage", false)
+        );
     }
 }
@@ -0,0 +1,179 @@
+use crate::{Affix, CraftItem, ModStat, ModStats, Rarity, RolledMod};
+use serde::{Deserialize, Serialize};
+
+/// Affix slots of an item and what could fill the open ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffixAnalysis {
+    pub rarity: Rarity,
+    pub prefixes: u8,
+    pub suffixes: u8,
+    /// Explicits of unknown affix type, see [`CraftItem::unknown_affixes`].
+    pub unknown: u8,
+    /// Open prefixes and suffixes may share slots with unknown explicits.
+    pub open_prefixes: u8,
+    pub open_suffixes: u8,
+    /// Best modifier for every open slot, prefixes first.
+    pub open_slots: Vec<OpenSlot>,
+    /// False when the item has explicits, which could not be classified for sure.
+    /// Open slots may then be fewer than listed.
+    pub reliable: bool,
+}
+
+/// Best damage modifier for an open affix slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenSlot {
+    pub affix: Affix,
+    pub mod_id: String,
+    /// Tier name, e.g. "Merciless".
+    pub name: String,
+    pub values: Vec<f32>,
+    /// DPS score after the slot is filled.
+    pub dps: f32,
+    /// DPS score gain over the item with previous slots filled.
+    pub dps_gain: f32,
+}
+
+impl CraftItem {
+    /// Fills open slots one by one with the highest tier damage modifier,
+    /// which gives the most DPS.
+    pub fn affix_analysis(&self) -> AffixAnalysis {
+        let mut item = self.clone();
+        let mut open_slots = vec![];
+
+        for affix in [Affix::Prefix, Affix::Suffix] {
+            while item.open_affixes(affix) > 0 {
+                let current = item.weapon.dps().score();
+                let best = ModStats::for_item_class(item.weapon.item_class)
+                    .filter(|stats| stats.affix == affix && stats.stat != ModStat::Other)
+                    .filter(|stats| !item.mods.iter().any(|m| m.mod_id == stats.id))
+                    .filter_map(|stats| {
+                        let rolled = stats.max_roll(item.weapon.item_level())?;
+                        let mut candidate = item.clone();
+                        candidate.add_mod(rolled.clone());
+                        Some((stats, rolled, candidate))
+                    })
+                    .max_by(|(_, _, a), (_, _, b)| {
+                        a.weapon
+                            .dps()
+                            .score()
+                            .partial_cmp(&b.weapon.dps().score())
+                            .unwrap()
+                    });
+
+                let Some((stats, rolled, candidate)) = best else {
+                    break;
+                };
+
+                let dps = candidate.weapon.dps().score();
+                open_slots.push(OpenSlot {
+                    affix,
+                    mod_id: rolled.mod_id,
+                    name: stats
+                        .tiers_at_level(item.weapon.item_level())
+                        .last()
+                        .map(|tier| tier.name.clone())
+                        .unwrap_or_default(),
+                    values: rolled.values,
+                    dps,
+                    dps_gain: dps - current,
+                });
+                item = candidate;
+            }
+        }
+
+        AffixAnalysis {
+            rarity: self.rarity,
+            prefixes: self.affixes(Affix::Prefix),
+            suffixes: self.affixes(Affix::Suffix),
+            unknown: self.unknown_affixes,
+            open_prefixes: self.open_affixes(Affix::Prefix),
+            open_suffixes: self.open_affixes(Affix::Suffix),
+            open_slots,
+            reliable: self.unknown_affixes == 0,
+        }
+    }
+
+    /// Adds modifier and updates weapon explicits.
+    pub fn add_mod(&mut self, rolled: RolledMod) {
+        self.mods.push(rolled);
+        self.update_explicits();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::ironwood_shortbow, AttackSpeedModifier, CastSpeedModifier, CasterExplicits,
+        Explicits, ItemClass, Quality, SpellDamageModifier, Weapon,
+    };
+
+    #[test]
+    fn open_slots() {
+        let weapon = ironwood_shortbow();
+        let item = CraftItem::new(weapon, Rarity::Rare).with_other_mods(1, 2);
+
+        let analysis = item.affix_analysis();
+        assert_eq!(2, analysis.prefixes);
+        assert_eq!(1, analysis.open_prefixes);
+        assert_eq!(1, analysis.open_suffixes);
+        assert_eq!(2, analysis.open_slots.len());
+
+        let prefix = &analysis.open_slots[0];
+        assert_eq!(Affix::Prefix, prefix.affix);
+        assert!(prefix.dps_gain > 0.0);
+        assert_eq!("LocalIncreasedAttackSpeed", analysis.open_slots[1].mod_id);
+        assert!(analysis.reliable);
+
+        // An unknown explicit takes one of the two open slots.
+        let analysis = item.with_unknown_mods(1).affix_analysis();
+        assert_eq!(1, analysis.open_slots.len());
+        assert!(!analysis.reliable);
+    }
+
+    #[test]
+    fn crit_suffix_adds_dps() {
+        let mut weapon = ironwood_shortbow();
+        weapon.explicits.atk_spd = Some(AttackSpeedModifier(19));
+        let item = CraftItem::new(weapon, Rarity::Rare).with_other_mods(2, 1);
+
+        let analysis = item.affix_analysis();
+        assert_eq!(1, analysis.open_slots.len());
+        let suffix = &analysis.open_slots[0];
+        assert!(suffix.mod_id.starts_with("LocalCriticalStrike"));
+        assert!(suffix.dps_gain > 0.0);
+    }
+
+    #[test]
+    fn caster_mods_add_score() {
+        let wand = Weapon {
+            base: "Volatile Wand".to_owned(),
+            item_class: ItemClass::Wands,
+            quality: Quality(20),
+            explicits: Explicits {
+                caster: Some(CasterExplicits {
+                    spell_damage: Some(SpellDamageModifier(69)),
+                    cast_speed: Some(CastSpeedModifier(18)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 1,
+            item_level: Some(54),
+        };
+        let item = CraftItem::new(wand.clone(), Rarity::Rare).with_other_mods(1, 0);
+        assert_eq!(3, item.mods.len());
+
+        let analysis = item.affix_analysis();
+        assert_eq!(2, analysis.open_slots.len());
+        assert!(analysis.open_slots.iter().all(|slot| slot.dps_gain > 0.0));
+        assert!(analysis
+            .open_slots
+            .iter()
+            .any(|slot| slot.mod_id == "SpellSkillLevels"));
+        assert!(wand.perfect().dps.score() > wand.dps().score());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{DamageType, Rune, RuneCasterBonus, Weapon};
+use serde::{Deserialize, Serialize};
+
+/// Approximate damage gained by a spell per gem level. Taken from spell gem level
+/// tables of the game data, where base damage of damaging spells grows by about 10%
+/// per level around the levels reached with +levels modifiers (15 to 20). Compounds
+/// per level, as the table does.
+pub const SPELL_DAMAGE_PER_LEVEL: f32 = 0.1;
+
+/// Explicits of caster weapons: wands, staves and sceptres.
+#[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq)]
+pub struct CasterExplicits {
+    pub spell_levels: Option<SpellLevels>,
+    pub spell_damage: Option<SpellDamageModifier>,
+    pub increased: Vec<IncreasedDamage>,
+    pub cast_speed: Option<CastSpeedModifier>,
+}
+
+/// +X to Level of all Spell Skills
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SpellLevels(pub u8);
+
+/// X% increased Spell Damage
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SpellDamageModifier(pub u16);
+
+/// X% increased Cast Speed
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CastSpeedModifier(pub u8);
+
+/// X% increased Fire Damage
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct IncreasedDamage {
+    pub damage_type: DamageType,
+    pub value: u16,
+}
+
+/// Damage multipliers the weapon gives to spells of each damage type,
+/// compared to casting the same spell with an empty weapon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasterScore {
+    pub physical: f32,
+    pub fire: f32,
+    pub cold: f32,
+    pub lightning: f32,
+    pub chaos: f32,
+    /// Multiplier of the best damage type.
+    pub score: f32,
+}
+
+impl CasterScore {
+    pub fn of_type(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Cold => self.cold,
+            DamageType::Lightning => self.lightning,
+            DamageType::Chaos => self.chaos,
+        }
+    }
+}
+
+impl Weapon {
+    pub fn is_caster(&self) -> bool {
+        self.item_class.is_caster()
+    }
+
+    /// Caster modifiers of explicits, implicits and enchants.
+    fn caster_modifiers(&self) -> impl Iterator<Item = &CasterExplicits> {
+        self.modifiers()
+            .into_iter()
+            .filter_map(|modifiers| modifiers.caster.as_ref())
+    }
+
+    /// Caster bonuses of socketed runes.
+    fn runes_caster_bonuses(&self) -> impl Iterator<Item = RuneCasterBonus> + '_ {
+        self.runes.iter().filter_map(Rune::caster)
+    }
+
+    /// Total % increased damage for spells of given type.
+    pub fn spell_increased(&self, damage_type: DamageType) -> u16 {
+        let explicit: u16 = self
+            .caster_modifiers()
+            .map(|caster| {
+                caster.spell_damage.unwrap_or_default().0
+                    + caster
+                        .increased
+                        .iter()
+                        .filter(|increased| increased.damage_type == damage_type)
+                        .map(|increased| increased.value)
+                        .sum::<u16>()
+            })
+            .sum();
+
+        let runes: u16 = self
+            .runes_caster_bonuses()
+            .map(|bonus| match bonus {
+                RuneCasterBonus::SpellDamage(spell_damage) => spell_damage.0,
+                RuneCasterBonus::Increased(increased) if increased.damage_type == damage_type => {
+                    increased.value
+                }
+                _ => 0,
+            })
+            .sum();
+
+        explicit + runes
+    }
+
+    pub fn cast_speed(&self) -> u16 {
+        let explicit: u16 = self
+            .caster_modifiers()
+            .map(|caster| caster.cast_speed.unwrap_or_default().0 as u16)
+            .sum();
+
+        let runes: u16 = self
+            .runes_caster_bonuses()
+            .map(|bonus| match bonus {
+                RuneCasterBonus::CastSpeed(cast_speed) => cast_speed.0 as u16,
+                _ => 0,
+            })
+            .sum();
+
+        explicit + runes
+    }
+
+    pub fn spell_levels(&self) -> u8 {
+        self.caster_modifiers()
+            .map(|caster| caster.spell_levels.unwrap_or_default().0)
+            .sum()
+    }
+
+    /// Spell damage multiplier for given damage type.
+    pub fn spell_multiplier(&self, damage_type: DamageType) -> f32 {
+        (1.0 + SPELL_DAMAGE_PER_LEVEL).powi(self.spell_levels() as i32)
+            * (1.0 + self.spell_increased(damage_type) as f32 / 100.0)
+            * (1.0 + self.cast_speed() as f32 / 100.0)
+    }
+
+    /// Caster evaluation. None for martial weapons.
+    pub fn caster_score(&self) -> Option<CasterScore> {
+        if !self.is_caster() {
+            return None;
+        }
+
+        let physical = self.spell_multiplier(DamageType::Physical);
+        let fire = self.spell_multiplier(DamageType::Fire);
+        let cold = self.spell_multiplier(DamageType::Cold);
+        let lightning = self.spell_multiplier(DamageType::Lightning);
+        let chaos = self.spell_multiplier(DamageType::Chaos);
+        let score = [physical, fire, cold, lightning, chaos]
+            .into_iter()
+            .fold(0.0, f32::max);
+
+        Some(CasterScore {
+            physical,
+            fire,
+            cold,
+            lightning,
+            chaos,
+            score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Explicits, ItemClass, Quality};
+
+    #[test]
+    fn caster_score() {
+        let wand = Weapon {
+            base: "Volatile Wand".to_owned(),
+            item_class: ItemClass::Wands,
+            quality: Quality(0),
+            explicits: Explicits {
+                caster: Some(CasterExplicits {
+                    spell_levels: Some(SpellLevels(1)),
+                    spell_damage: Some(SpellDamageModifier(50)),
+                    increased: vec![IncreasedDamage {
+                        damage_type: DamageType::Fire,
+                        value: 30,
+                    }],
+                    cast_speed: None,
+                }),
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![Rune::from_id("Desert").unwrap()],
+            sockets: 1,
+            item_level: None,
+        };
+
+        let score = wand.caster_score().unwrap();
+        assert!((1.1 * 2.05 - score.fire).abs() < 0.0001);
+        assert!((1.1 * 1.5 - score.cold).abs() < 0.0001);
+        assert_eq!(score.fire, score.score);
+        assert!(wand.with_different_runes()[0]
+            .runes
+            .iter()
+            .all(|rune| rune.caster().is_some()));
+    }
+}
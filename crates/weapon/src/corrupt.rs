@@ -0,0 +1,214 @@
+use crate::{Affix, Dps, DpsWithRunes, ModStat, Quality, RollRange, RolledMod, Weapon};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Corruption odds shipped with the crate, `data/corruption.json`.
+///
+/// The bundled odds are placeholders, as the game does not publish them: outcomes are
+/// equally likely, as are enchants, whose roll ranges are estimates. Use
+/// [`Weapon::corruption_outcomes_with`] with measured odds, when they are known.
+pub static CORRUPTION: Lazy<CorruptionTable> =
+    Lazy::new(|| CorruptionTable::from_json(include_str!("../data/corruption.json")).unwrap());
+
+/// Quality change of corruption, up or down with the same chance.
+pub const CORRUPTION_QUALITY_CHANGE: u8 = 10;
+/// Quality limit of corrupted items.
+pub const MAX_CORRUPTED_QUALITY: u8 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorruptionKind {
+    /// Corrupted without changes, can no longer be crafted.
+    Bricked,
+    ExtraSocket,
+    Enchant,
+    Quality,
+}
+
+/// Chances of corruption outcomes and enchants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorruptionTable {
+    /// Where the odds come from.
+    pub source: String,
+    /// Chance of every kind of outcome. Sum up to 1.
+    pub outcomes: Vec<CorruptionChance>,
+    pub enchants: Vec<CorruptionEnchant>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CorruptionChance {
+    pub kind: CorruptionKind,
+    pub chance: f32,
+}
+
+/// Enchant corruption can add to a weapon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorruptionEnchant {
+    /// Enchant line with values replaced by #.
+    pub text: String,
+    pub stat: ModStat,
+    /// Relative chance among enchants.
+    pub weight: u32,
+    pub rolls: Vec<RollRange>,
+}
+
+impl CorruptionTable {
+    pub fn from_json(s: &str) -> Result<CorruptionTable, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorruptionOutcome {
+    pub kind: CorruptionKind,
+    /// Added enchant, e.g. "#% increased Physical Damage".
+    pub enchant: Option<String>,
+    pub probability: f32,
+    pub weapon: Weapon,
+    pub dps: Dps,
+    /// Best rune fill of all sockets, including an added one.
+    pub best_runes: Option<DpsWithRunes>,
+}
+
+impl Weapon {
+    /// Every outcome of corrupting the weapon with bundled odds, see [`CORRUPTION`].
+    pub fn corruption_outcomes(&self) -> Vec<CorruptionOutcome> {
+        self.corruption_outcomes_with(&CORRUPTION)
+    }
+
+    /// Every outcome of corrupting the weapon. Probabilities sum up to 1.
+    /// Extra socket of a weapon, which already has one, leaves it unchanged
+    /// and counts as bricked. Enchants are valued at the middle of their rolls.
+    pub fn corruption_outcomes_with(&self, table: &CorruptionTable) -> Vec<CorruptionOutcome> {
+        let mut outcomes = vec![];
+        let mut bricked = 0.0;
+
+        for &CorruptionChance { kind, chance } in &table.outcomes {
+            match kind {
+                CorruptionKind::Bricked => bricked += chance,
+                CorruptionKind::ExtraSocket if self.sockets > self.handed().default_sockets() => {
+                    bricked += chance
+                }
+                CorruptionKind::ExtraSocket => {
+                    let weapon = Weapon {
+                        sockets: self.sockets + 1,
+                        ..self.clone()
+                    };
+                    outcomes.push(outcome(kind, None, chance, weapon));
+                }
+                CorruptionKind::Enchant => {
+                    let total_weight: u32 = table.enchants.iter().map(|e| e.weight).sum();
+                    for enchant in &table.enchants {
+                        let mut weapon = self.clone();
+                        weapon.enchants.set_mod(&RolledMod {
+                            mod_id: enchant.text.clone(),
+                            stat: enchant.stat,
+                            affix: Affix::Prefix,
+                            values: enchant
+                                .rolls
+                                .iter()
+                                .map(|roll| (roll.0 + roll.1) / 2.0)
+                                .collect(),
+                        });
+                        let chance = chance * enchant.weight as f32 / total_weight.max(1) as f32;
+                        outcomes.push(outcome(kind, Some(enchant.text.clone()), chance, weapon));
+                    }
+                }
+                CorruptionKind::Quality => {
+                    let up = Quality(
+                        (self.quality.0 + CORRUPTION_QUALITY_CHANGE).min(MAX_CORRUPTED_QUALITY),
+                    );
+                    let down = Quality(self.quality.0.saturating_sub(CORRUPTION_QUALITY_CHANGE));
+                    for quality in [up, down] {
+                        let weapon = Weapon {
+                            quality,
+                            ..self.clone()
+                        };
+                        outcomes.push(outcome(kind, None, chance / 2.0, weapon));
+                    }
+                }
+            }
+        }
+
+        if bricked > 0.0 {
+            outcomes.insert(
+                0,
+                outcome(CorruptionKind::Bricked, None, bricked, self.clone()),
+            );
+        }
+
+        outcomes
+    }
+
+    /// Probability weighted [`Dps::score`] of corruption outcomes.
+    pub fn expected_corrupted_score(&self) -> f32 {
+        self.corruption_outcomes()
+            .iter()
+            .map(|outcome| outcome.probability * outcome.dps.score())
+            .sum()
+    }
+}
+
+fn outcome(
+    kind: CorruptionKind,
+    enchant: Option<String>,
+    probability: f32,
+    weapon: Weapon,
+) -> CorruptionOutcome {
+    CorruptionOutcome {
+        kind,
+        enchant,
+        probability,
+        dps: weapon.dps(),
+        best_runes: weapon.with_different_runes().into_iter().next(),
+        weapon,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::ironwood_shortbow;
+
+    #[test]
+    fn corruption_outcomes() {
+        let weapon = ironwood_shortbow();
+
+        let outcomes = weapon.corruption_outcomes();
+        let total: f32 = outcomes.iter().map(|o| o.probability).sum();
+        assert!((1.0 - total).abs() < 0.0001);
+
+        let socket = outcomes
+            .iter()
+            .find(|o| o.kind == CorruptionKind::ExtraSocket)
+            .unwrap();
+        assert_eq!(3, socket.weapon.sockets);
+        assert_eq!(3, socket.best_runes.as_ref().unwrap().runes.len());
+
+        let phys = outcomes
+            .iter()
+            .find(|o| o.enchant.as_deref() == Some("#% increased Physical Damage"))
+            .unwrap();
+        assert!(phys.dps.total > weapon.dps().total);
+    }
+
+    #[test]
+    fn extra_socket_above_default_is_bricked() {
+        let weapon = Weapon {
+            sockets: 3,
+            ..ironwood_shortbow()
+        };
+
+        let outcomes = weapon.corruption_outcomes();
+        assert!(outcomes
+            .iter()
+            .all(|o| o.kind != CorruptionKind::ExtraSocket));
+        let bricked = outcomes
+            .iter()
+            .find(|o| o.kind == CorruptionKind::Bricked)
+            .unwrap();
+        assert!((0.5 - bricked.probability).abs() < 0.0001);
+        let total: f32 = outcomes.iter().map(|o| o.probability).sum();
+        assert!((1.0 - total).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,471 @@
+use crate::{
+    Affix, AttackSpeedModifier, CastSpeedModifier, CasterExplicits, CritChanceModifier,
+    CritDamageModifier, Explicits, FlatDamage, IncreasedDamage, ModStat, ModStats, PhysModifier,
+    Range, SpellDamageModifier, SpellLevels, Weapon, MOD_DATA_SOURCED,
+};
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    #[default]
+    Normal,
+    Magic,
+    Rare,
+    Unique,
+}
+
+impl Rarity {
+    /// Maximum number of prefixes, the same for suffixes.
+    pub fn max_affixes(&self) -> u8 {
+        match self {
+            Rarity::Normal => 0,
+            Rarity::Magic => 1,
+            Rarity::Rare => 3,
+            Rarity::Unique => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Currency {
+    /// Normal to magic with one modifier.
+    Transmutation,
+    /// Adds a modifier to a magic item.
+    Augmentation,
+    /// Magic to rare, adding a modifier.
+    Regal,
+    /// Adds a modifier to a rare item.
+    Exalted,
+    /// Replaces a random modifier of a rare item.
+    Chaos,
+}
+
+/// Id of damage modifiers, whose values fit no tier of mods.json.
+pub const UNTIERED_MOD_ID: &str = "Untiered";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CraftError {
+    WrongRarity(Currency, Rarity),
+    NoOpenAffix,
+    NoModifiers,
+}
+
+/// Modifier rolled on an item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolledMod {
+    pub mod_id: String,
+    pub stat: ModStat,
+    pub affix: Affix,
+    pub values: Vec<f32>,
+}
+
+/// Weapon with its rarity and explicit modifiers, which currency is applied to.
+#[derive(Debug, Clone)]
+pub struct CraftItem {
+    pub weapon: Weapon,
+    pub rarity: Rarity,
+    pub mods: Vec<RolledMod>,
+    /// Explicits not in mods.json. Each occupies a prefix or a suffix slot.
+    pub unknown_affixes: u8,
+}
+
+impl CraftItem {
+    /// Takes damage modifiers from weapon explicits. Values outside of known tiers,
+    /// e.g. of hybrid modifiers, are kept as [`UNTIERED_MOD_ID`] modifiers.
+    /// Other explicits are not visible to the model, see [`CraftItem::with_other_mods`].
+    pub fn new(weapon: Weapon, rarity: Rarity) -> CraftItem {
+        let tiered = weapon.explicit_tiers();
+        let untiered: Vec<RolledMod> = weapon
+            .explicits
+            .mod_values()
+            .into_iter()
+            .filter(|(stat, _)| !tiered.iter().any(|info| info.stat == *stat))
+            .filter_map(|(stat, values)| {
+                Some(RolledMod {
+                    mod_id: UNTIERED_MOD_ID.to_owned(),
+                    stat,
+                    affix: stat.affix()?,
+                    values,
+                })
+            })
+            .collect();
+        let mods = tiered
+            .into_iter()
+            .map(|info| RolledMod {
+                mod_id: info.mod_id,
+                stat: info.stat,
+                affix: info.affix,
+                values: info.values,
+            })
+            .chain(untiered)
+            .collect();
+
+        let mut item = CraftItem {
+            weapon,
+            rarity,
+            mods,
+            unknown_affixes: 0,
+        };
+        item.update_explicits();
+        item
+    }
+
+    /// Occupies affix slots with modifiers that do not affect damage.
+    pub fn with_other_mods(mut self, prefixes: u8, suffixes: u8) -> CraftItem {
+        let unknown = |affix| RolledMod {
+            mod_id: "Unknown".to_owned(),
+            stat: ModStat::Other,
+            affix,
+            values: vec![],
+        };
+        self.mods
+            .extend((0..prefixes).map(|_| unknown(Affix::Prefix)));
+        self.mods
+            .extend((0..suffixes).map(|_| unknown(Affix::Suffix)));
+        self
+    }
+
+    /// Occupies affix slots with modifiers of unknown affix type.
+    pub fn with_unknown_mods(mut self, count: u8) -> CraftItem {
+        self.unknown_affixes += count;
+        self
+    }
+
+    pub fn affixes(&self, affix: Affix) -> u8 {
+        self.mods.iter().filter(|m| m.affix == affix).count() as u8
+    }
+
+    /// Open slots of the affix type. Unknown modifiers may occupy either type,
+    /// so prefixes and suffixes share the slots they leave.
+    pub fn open_affixes(&self, affix: Affix) -> u8 {
+        let max = self.rarity.max_affixes();
+        let open = (max * 2).saturating_sub(self.mods.len() as u8 + self.unknown_affixes);
+        max.saturating_sub(self.affixes(affix)).min(open)
+    }
+
+    pub fn apply(&mut self, currency: Currency, rng: &mut Rng) -> Result<(), CraftError> {
+        let wrong_rarity = CraftError::WrongRarity(currency, self.rarity);
+        match currency {
+            Currency::Transmutation => {
+                if self.rarity != Rarity::Normal {
+                    return Err(wrong_rarity);
+                }
+                self.rarity = Rarity::Magic;
+                self.add_random_mod(rng)?;
+            }
+            Currency::Augmentation => {
+                if self.rarity != Rarity::Magic {
+                    return Err(wrong_rarity);
+                }
+                self.add_random_mod(rng)?;
+            }
+            Currency::Regal => {
+                if self.rarity != Rarity::Magic {
+                    return Err(wrong_rarity);
+                }
+                self.rarity = Rarity::Rare;
+                self.add_random_mod(rng)?;
+            }
+            Currency::Exalted => {
+                if self.rarity != Rarity::Rare {
+                    return Err(wrong_rarity);
+                }
+                self.add_random_mod(rng)?;
+            }
+            Currency::Chaos => {
+                if self.rarity != Rarity::Rare {
+                    return Err(wrong_rarity);
+                }
+                if self.mods.is_empty() {
+                    return Err(CraftError::NoModifiers);
+                }
+                self.mods.remove(rng.usize(..self.mods.len()));
+                self.add_random_mod(rng)?;
+            }
+        }
+
+        self.update_explicits();
+        Ok(())
+    }
+
+    /// Picks a modifier tier by weight among those, which can roll on the item.
+    fn add_random_mod(&mut self, rng: &mut Rng) -> Result<(), CraftError> {
+        let item_level = self.weapon.item_level();
+        let candidates: Vec<_> = ModStats::for_item_class(self.weapon.item_class)
+            .filter(|stats| self.open_affixes(stats.affix) > 0)
+            .filter(|stats| !self.mods.iter().any(|m| m.mod_id == stats.id))
+            .flat_map(|stats| {
+                stats
+                    .tiers_at_level(item_level)
+                    .map(move |tier| (stats, tier))
+            })
+            .collect();
+
+        let total_weight: u32 = candidates.iter().map(|(_, tier)| tier.weight).sum();
+        if total_weight == 0 {
+            return Err(CraftError::NoOpenAffix);
+        }
+
+        let mut roll = rng.u32(..total_weight);
+        let mut picked = candidates[0];
+        for (stats, tier) in candidates {
+            if roll < tier.weight {
+                picked = (stats, tier);
+                break;
+            }
+            roll -= tier.weight;
+        }
+        let (stats, tier) = picked;
+
+        let values = tier
+            .rolls
+            .iter()
+            .map(|range| range.random(rng, stats.stat.step()))
+            .collect();
+
+        self.mods.push(RolledMod {
+            mod_id: stats.id.clone(),
+            stat: stats.stat,
+            affix: stats.affix,
+            values,
+        });
+
+        Ok(())
+    }
+
+    /// Rebuilds weapon explicits from rolled modifiers.
+    pub(crate) fn update_explicits(&mut self) {
+        let mut explicits = Explicits::from_mods(&self.mods);
+        if self.weapon.is_caster() {
+            explicits
+                .caster
+                .get_or_insert_with(CasterExplicits::default);
+        }
+        self.weapon.explicits = explicits;
+    }
+}
+
+impl Explicits {
+    /// Damage explicits of rolled modifiers. Values of the same stat are summed up,
+    /// as on the item.
+    pub fn from_mods(mods: &[RolledMod]) -> Explicits {
+        let mut explicits = Explicits::default();
+        for m in mods {
+            let mut values = m.values.clone();
+            let current = explicits
+                .mod_values()
+                .into_iter()
+                .find(|(stat, _)| *stat == m.stat);
+            if let Some((_, current)) = current {
+                values.iter_mut().zip(current).for_each(|(v, c)| *v += c);
+            }
+            explicits.set_mod(&RolledMod {
+                values,
+                ..m.clone()
+            });
+        }
+
+        explicits
+    }
+
+    fn caster_mut(&mut self) -> &mut CasterExplicits {
+        self.caster.get_or_insert_with(CasterExplicits::default)
+    }
+
+    /// Sets value of the modifier stat, replacing the previous one.
+    pub fn set_mod(&mut self, m: &RolledMod) {
+        match m.stat {
+            ModStat::Phys => self.phys = Some(PhysModifier(m.values[0] as u16)),
+            ModStat::Flat(damage_type) => {
+                let flat = FlatDamage {
+                    damage_type,
+                    range: Range(m.values[0] as u16, m.values[1] as u16),
+                };
+                match self
+                    .flats
+                    .iter_mut()
+                    .find(|flat| flat.damage_type == damage_type)
+                {
+                    Some(existing) => *existing = flat,
+                    None => self.flats.push(flat),
+                }
+            }
+            ModStat::AttackSpeed => self.atk_spd = Some(AttackSpeedModifier(m.values[0] as u8)),
+            ModStat::CritChance => self.crit_chance = Some(CritChanceModifier(m.values[0])),
+            ModStat::CritDamage => self.crit_dmg = Some(CritDamageModifier(m.values[0] as u16)),
+            ModStat::SpellLevels => {
+                self.caster_mut().spell_levels = Some(SpellLevels(m.values[0] as u8))
+            }
+            ModStat::SpellDamage => {
+                self.caster_mut().spell_damage = Some(SpellDamageModifier(m.values[0] as u16))
+            }
+            ModStat::Increased(damage_type) => {
+                let increased = IncreasedDamage {
+                    damage_type,
+                    value: m.values[0] as u16,
+                };
+                let caster = self.caster_mut();
+                match caster
+                    .increased
+                    .iter_mut()
+                    .find(|increased| increased.damage_type == damage_type)
+                {
+                    Some(existing) => *existing = increased,
+                    None => caster.increased.push(increased),
+                }
+            }
+            ModStat::CastSpeed => {
+                self.caster_mut().cast_speed = Some(CastSpeedModifier(m.values[0] as u8))
+            }
+            ModStat::Other => {}
+        }
+    }
+}
+
+/// Distribution of DPS after crafting, see [`simulate_crafting`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CraftOutcome {
+    pub trials: usize,
+    pub mean: f32,
+    pub p10: f32,
+    pub p25: f32,
+    pub median: f32,
+    pub p75: f32,
+    pub p90: f32,
+    /// Share of trials, which reached target DPS.
+    pub chance_to_exceed: Option<f32>,
+    /// Currency applications of all trials, which could not be applied and were skipped,
+    /// e.g. Exalted Orb on an item without open affixes.
+    pub skipped: usize,
+    /// Odds come from placeholder spawn weights and tiers, see [`MOD_DATA_SOURCED`].
+    /// Illustrative outcomes must not be used to decide whether to craft.
+    pub illustrative: bool,
+}
+
+/// Applies currencies in order to copies of the item and collects resulting
+/// [`Dps::score`](crate::Dps::score). Currency, which cannot be applied at that step, is skipped
+/// and counted in [`CraftOutcome::skipped`]. Outcomes are illustrative while mods.json is not
+/// game data, see [`CraftOutcome::illustrative`].
+pub fn simulate_crafting(
+    item: &CraftItem,
+    currencies: &[Currency],
+    trials: usize,
+    target: Option<f32>,
+    seed: u64,
+) -> CraftOutcome {
+    let mut rng = Rng::with_seed(seed);
+    let mut skipped = 0;
+    let mut scores: Vec<f32> = (0..trials)
+        .map(|_| {
+            let mut item = item.clone();
+            for currency in currencies {
+                if item.apply(*currency, &mut rng).is_err() {
+                    skipped += 1;
+                }
+            }
+            item.weapon.dps().score()
+        })
+        .collect();
+    scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let percentile = |p: f32| match scores.is_empty() {
+        true => 0.0,
+        false => scores[((scores.len() - 1) as f32 * p).round() as usize],
+    };
+
+    CraftOutcome {
+        trials,
+        mean: scores.iter().sum::<f32>() / trials.max(1) as f32,
+        p10: percentile(0.1),
+        p25: percentile(0.25),
+        median: percentile(0.5),
+        p75: percentile(0.75),
+        p90: percentile(0.9),
+        chance_to_exceed: target.map(|target| {
+            scores.iter().filter(|score| **score >= target).count() as f32 / trials.max(1) as f32
+        }),
+        skipped,
+        illustrative: !MOD_DATA_SOURCED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::ironwood_shortbow;
+
+    fn normal_bow() -> Weapon {
+        Weapon {
+            explicits: Explicits::default(),
+            ..ironwood_shortbow()
+        }
+    }
+
+    #[test]
+    fn apply_currencies() {
+        let mut rng = Rng::with_seed(7);
+        let mut item = CraftItem::new(normal_bow(), Rarity::Normal);
+
+        assert_eq!(
+            Err(CraftError::WrongRarity(Currency::Regal, Rarity::Normal)),
+            item.apply(Currency::Regal, &mut rng)
+        );
+
+        item.apply(Currency::Transmutation, &mut rng).unwrap();
+        item.apply(Currency::Augmentation, &mut rng).unwrap();
+        item.apply(Currency::Regal, &mut rng).unwrap();
+        item.apply(Currency::Exalted, &mut rng).unwrap();
+        item.apply(Currency::Exalted, &mut rng).unwrap();
+        item.apply(Currency::Exalted, &mut rng).unwrap();
+
+        assert_eq!(Rarity::Rare, item.rarity);
+        assert_eq!(0, item.open_affixes(Affix::Prefix));
+        assert_eq!(0, item.open_affixes(Affix::Suffix));
+        assert_eq!(
+            Err(CraftError::NoOpenAffix),
+            item.apply(Currency::Exalted, &mut rng)
+        );
+
+        item.apply(Currency::Chaos, &mut rng).unwrap();
+        assert_eq!(6, item.mods.len());
+    }
+
+    #[test]
+    fn dps_distribution() {
+        let item = CraftItem::new(normal_bow(), Rarity::Normal);
+        let base = item.weapon.dps().score();
+        let outcome = simulate_crafting(
+            &item,
+            &[
+                Currency::Transmutation,
+                Currency::Augmentation,
+                Currency::Regal,
+            ],
+            500,
+            Some(base),
+            1,
+        );
+
+        assert!(outcome.p10 <= outcome.median && outcome.median <= outcome.p90);
+        assert!(outcome.mean > base);
+        assert_eq!(Some(1.0), outcome.chance_to_exceed);
+        assert!(outcome.illustrative);
+    }
+
+    #[test]
+    fn untiered_values_are_kept() {
+        // 200% is above the highest tier, as with a hybrid modifier added on top.
+        let mut weapon = ironwood_shortbow();
+        weapon.explicits.phys = Some(PhysModifier(200));
+        let item = CraftItem::new(weapon.clone(), Rarity::Rare);
+
+        assert_eq!(UNTIERED_MOD_ID, item.mods[0].mod_id);
+        assert_eq!(weapon.dps().total, item.weapon.dps().total);
+
+        let outcome = simulate_crafting(&item, &[Currency::Exalted; 7], 10, None, 1);
+        assert_eq!(10 * 2, outcome.skipped);
+    }
+}
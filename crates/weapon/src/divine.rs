@@ -0,0 +1,98 @@
+use crate::{Dps, ModTierInfo, RollRange, RolledMod, Weapon};
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// DPS of the weapon with explicit values rerolled within their tiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivineOutcome {
+    pub current: Dps,
+    /// Every value at the bottom of its tier.
+    pub min: Dps,
+    /// Every value at the top of its tier.
+    pub max: Dps,
+    pub trials: usize,
+    /// [`Dps::score`] distribution of random rerolls.
+    pub mean: f32,
+    pub p10: f32,
+    pub median: f32,
+    pub p90: f32,
+    /// Share of rerolls better than the current weapon.
+    pub chance_to_improve: f32,
+}
+
+impl Weapon {
+    /// Outcomes of rerolling explicit values, as Divine Orb does.
+    /// Explicits which do not fit a known tier keep their values.
+    pub fn divine(&self, trials: usize, seed: u64) -> DivineOutcome {
+        let tiers = self.explicit_tiers();
+        let with_values = |value: &mut dyn FnMut(&ModTierInfo, &RollRange) -> f32| {
+            let mut weapon = self.clone();
+            for info in &tiers {
+                weapon.explicits.set_mod(&RolledMod {
+                    mod_id: info.mod_id.clone(),
+                    stat: info.stat,
+                    affix: info.affix,
+                    values: info.rolls.iter().map(|roll| value(info, roll)).collect(),
+                });
+            }
+            weapon
+        };
+
+        let current = self.dps();
+        let min = with_values(&mut |_, roll| roll.0).dps();
+        let max = with_values(&mut |_, roll| roll.1).dps();
+
+        let mut rng = Rng::with_seed(seed);
+        let mut scores: Vec<f32> = (0..trials)
+            .map(|_| {
+                with_values(&mut |info, roll| roll.random(&mut rng, info.stat.step()))
+                    .dps()
+                    .score()
+            })
+            .collect();
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let percentile = |p: f32| match scores.is_empty() {
+            true => current.score(),
+            false => scores[((scores.len() - 1) as f32 * p).round() as usize],
+        };
+
+        DivineOutcome {
+            mean: scores.iter().sum::<f32>() / trials.max(1) as f32,
+            p10: percentile(0.1),
+            median: percentile(0.5),
+            p90: percentile(0.9),
+            chance_to_improve: scores
+                .iter()
+                .filter(|score| **score > current.score())
+                .count() as f32
+                / trials.max(1) as f32,
+            trials,
+            current,
+            min,
+            max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::ironwood_shortbow, AttackSpeedModifier, DamageType, FlatDamage, Range};
+
+    #[test]
+    fn divine_range() {
+        let mut weapon = ironwood_shortbow();
+        weapon.explicits.flats.push(FlatDamage {
+            damage_type: DamageType::Physical,
+            range: Range(12, 18),
+        });
+        weapon.explicits.atk_spd = Some(AttackSpeedModifier(19));
+
+        let outcome = weapon.divine(200, 3);
+        assert!(outcome.min.score() < outcome.current.score());
+        assert!(outcome.current.score() < outcome.max.score());
+        assert!(outcome.min.score() <= outcome.p10 && outcome.p10 < outcome.p90);
+        assert!(outcome.p90 <= outcome.max.score());
+        assert!(outcome.chance_to_improve > 0.0 && outcome.chance_to_improve < 1.0);
+    }
+}
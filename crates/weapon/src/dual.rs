@@ -0,0 +1,174 @@
+use crate::{BuildProfile, DamageType, Dps, FlatDamage, Handed, ItemClass, Range, Rune, Weapon};
+use serde::{Deserialize, Serialize};
+
+/// Two one-handed weapons. Attacks alternate between them, hit by hit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DualWield {
+    pub main_hand: Weapon,
+    pub off_hand: Weapon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DualWieldError {
+    /// Two-handed and caster weapons can not be dual wielded.
+    CanNotDualWield(ItemClass),
+}
+
+/// Runes of both weapons with the combined DPS.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DualWieldRunes {
+    pub main_hand: Vec<Rune>,
+    pub off_hand: Vec<Rune>,
+    pub dps: Dps,
+}
+
+impl ItemClass {
+    pub fn can_dual_wield(&self) -> bool {
+        matches!(self.handed(), Handed::OneHanded) && !self.is_caster()
+    }
+}
+
+impl DualWield {
+    pub fn new(main_hand: Weapon, off_hand: Weapon) -> Result<DualWield, DualWieldError> {
+        for weapon in [&main_hand, &off_hand] {
+            if !weapon.item_class.can_dual_wield() {
+                return Err(DualWieldError::CanNotDualWield(weapon.item_class));
+            }
+        }
+
+        Ok(DualWield {
+            main_hand,
+            off_hand,
+        })
+    }
+
+    pub fn dps(&self) -> Dps {
+        self.dps_with_profile(&BuildProfile::default())
+    }
+
+    pub fn dps_with_profile(&self, profile: &BuildProfile) -> Dps {
+        alternate(
+            &self.main_hand,
+            &self.main_hand.dps_with_profile(profile),
+            &self.off_hand,
+            &self.off_hand.dps_with_profile(profile),
+        )
+    }
+
+    /// Every rune fill of both weapons, optimized jointly. Best combined DPS first.
+    pub fn with_different_runes(&self, profile: &BuildProfile) -> Vec<DualWieldRunes> {
+        let fills = |weapon: &Weapon| -> Vec<(Weapon, Dps)> {
+            weapon
+                .rune_fills()
+                .map(|weapon| {
+                    let dps = weapon.dps_with_profile(profile);
+                    (weapon, dps)
+                })
+                .collect()
+        };
+        let main_fills = fills(&self.main_hand);
+        let off_fills = fills(&self.off_hand);
+
+        let mut vec = vec![];
+        for (main_hand, main_dps) in &main_fills {
+            for (off_hand, off_dps) in &off_fills {
+                vec.push(DualWieldRunes {
+                    main_hand: main_hand.runes.clone(),
+                    off_hand: off_hand.runes.clone(),
+                    dps: alternate(main_hand, main_dps, off_hand, off_dps),
+                });
+            }
+        }
+        vec.sort_by(|a, b| b.dps.score().partial_cmp(&a.dps.score()).unwrap());
+
+        vec
+    }
+}
+
+/// Combined DPS of alternating hits. Every hit takes the attack time of its weapon,
+/// so DPS values are averaged weighted by attack time.
+fn alternate(main_hand: &Weapon, main_dps: &Dps, off_hand: &Weapon, off_dps: &Dps) -> Dps {
+    let main_time = 1.0 / main_hand.attacks_per_second();
+    let off_time = 1.0 / off_hand.attacks_per_second();
+    let main_weight = main_time / (main_time + off_time);
+    let off_weight = 1.0 - main_weight;
+    let average = |main: f32, off: f32| main * main_weight + off * off_weight;
+
+    let hit_range = |dps: &Dps, damage_type: DamageType| {
+        dps.damages
+            .iter()
+            .find(|flat| flat.damage_type == damage_type)
+            .map(|flat| flat.range)
+            .unwrap_or_default()
+    };
+
+    Dps {
+        total: average(main_dps.total, off_dps.total),
+        edps: average(main_dps.edps, off_dps.edps),
+        pdps: average(main_dps.pdps, off_dps.pdps),
+        cdps: average(main_dps.cdps, off_dps.cdps),
+        fire: average(main_dps.fire, off_dps.fire),
+        cold: average(main_dps.cold, off_dps.cold),
+        lightning: average(main_dps.lightning, off_dps.lightning),
+        // Average hit, both weapons hit equally often.
+        damages: DamageType::ALL
+            .into_iter()
+            .map(|damage_type| {
+                let main = hit_range(main_dps, damage_type);
+                let off = hit_range(off_dps, damage_type);
+                FlatDamage {
+                    damage_type,
+                    range: Range(
+                        ((main.0 + off.0) as f32 / 2.0).round() as u16,
+                        ((main.1 + off.1) as f32 / 2.0).round() as u16,
+                    ),
+                }
+            })
+            .filter(|flat| flat.range.sum() > 0)
+            .collect(),
+        crit_chance: (main_dps.crit_chance + off_dps.crit_chance) / 2.0,
+        total_with_crit: average(main_dps.total_with_crit, off_dps.total_with_crit),
+        caster: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::ironwood_shortbow, Explicits, PhysModifier};
+
+    #[test]
+    fn dual_wield() {
+        let club = |phys: u16| Weapon {
+            base: "Spiked Club".to_owned(),
+            item_class: ItemClass::OneHandMaces,
+            explicits: Explicits {
+                phys: Some(PhysModifier(phys)),
+                ..Default::default()
+            },
+            sockets: 1,
+            ..ironwood_shortbow()
+        };
+        let mace = club(100);
+        let other = club(50);
+
+        let same = DualWield::new(mace.clone(), mace.clone()).unwrap();
+        assert!((mace.dps().total - same.dps().total).abs() < 0.01);
+
+        let pair = DualWield::new(mace.clone(), other.clone()).unwrap();
+        let expected = (mace.dps().total + other.dps().total) / 2.0;
+        assert!((expected - pair.dps().total).abs() < 0.01);
+
+        let runes = pair.with_different_runes(&BuildProfile::default());
+        assert_eq!(
+            mace.rune_fills().count() * other.rune_fills().count(),
+            runes.len()
+        );
+        assert!(runes[0].dps.total > pair.dps().total);
+
+        assert_eq!(
+            Err(DualWieldError::CanNotDualWield(ItemClass::Bows)),
+            DualWield::new(mace, ironwood_shortbow()).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{BuildProfile, DamageType, Dps, FlatDamage, Range, Weapon};
+use serde::{Deserialize, Serialize};
+
+/// Highest resistance enemies can have, in percents.
+pub const MAX_ENEMY_RESISTANCE: i16 = 75;
+/// Highest share of physical hit damage armour can prevent.
+pub const MAX_ARMOUR_REDUCTION: f32 = 0.9;
+
+/// Defences of the target the damage is dealt to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Enemy {
+    pub name: String,
+    pub fire_resistance: i16,
+    pub cold_resistance: i16,
+    pub lightning_resistance: i16,
+    pub chaos_resistance: i16,
+    pub armour: u32,
+    /// Lowers fire, cold and lightning resistances, can make them negative.
+    pub exposure: u16,
+}
+
+impl Enemy {
+    pub fn normal_mob() -> Enemy {
+        Enemy {
+            name: "Normal map monster".to_owned(),
+            armour: 300,
+            ..Default::default()
+        }
+    }
+
+    pub fn rare_mob() -> Enemy {
+        Enemy {
+            name: "Rare map monster".to_owned(),
+            fire_resistance: 30,
+            cold_resistance: 30,
+            lightning_resistance: 30,
+            chaos_resistance: 15,
+            armour: 800,
+            exposure: 0,
+        }
+    }
+
+    pub fn pinnacle_boss() -> Enemy {
+        Enemy {
+            name: "Pinnacle boss".to_owned(),
+            fire_resistance: 50,
+            cold_resistance: 50,
+            lightning_resistance: 50,
+            chaos_resistance: 30,
+            armour: 1500,
+            exposure: 0,
+        }
+    }
+
+    pub fn presets() -> Vec<Enemy> {
+        vec![
+            Enemy::normal_mob(),
+            Enemy::rare_mob(),
+            Enemy::pinnacle_boss(),
+        ]
+    }
+
+    /// Resistance after exposure and penetration. Penetration does not lower it below zero.
+    pub fn effective_resistance(&self, damage_type: DamageType, penetration: u16) -> i16 {
+        let resistance = match damage_type {
+            DamageType::Physical => return 0,
+            DamageType::Fire => self.fire_resistance,
+            DamageType::Cold => self.cold_resistance,
+            DamageType::Lightning => self.lightning_resistance,
+            DamageType::Chaos => self.chaos_resistance,
+        };
+        let resistance = match damage_type.is_elemental() {
+            true => resistance - self.exposure as i16,
+            false => resistance,
+        }
+        .min(MAX_ENEMY_RESISTANCE);
+
+        match resistance > 0 {
+            true => (resistance - penetration as i16).max(0),
+            false => resistance,
+        }
+    }
+
+    /// Share of physical hit damage prevented by armour.
+    pub fn armour_reduction(&self, hit: f32) -> f32 {
+        if hit <= 0.0 {
+            return 0.0;
+        }
+        let armour = self.armour as f32;
+        (armour / (armour + 10.0 * hit)).min(MAX_ARMOUR_REDUCTION)
+    }
+
+    /// Share of damage of the type which is dealt, for the average hit.
+    pub fn damage_taken(&self, damage_type: DamageType, hit: f32, penetration: u16) -> f32 {
+        match damage_type {
+            DamageType::Physical => 1.0 - self.armour_reduction(hit),
+            _ => 1.0 - self.effective_resistance(damage_type, penetration) as f32 / 100.0,
+        }
+    }
+}
+
+impl Dps {
+    /// DPS dealt to the enemy after its resistances and armour.
+    /// Caster score is not affected.
+    pub fn against(&self, enemy: &Enemy, profile: &BuildProfile) -> Dps {
+        let taken = |damage_type: DamageType| {
+            let hit = self
+                .damages
+                .iter()
+                .find(|flat| flat.damage_type == damage_type)
+                .map(|flat| (flat.range.0 + flat.range.1) as f32 / 2.0)
+                .unwrap_or_default();
+            enemy.damage_taken(damage_type, hit, profile.penetration(damage_type))
+        };
+
+        let pdps = self.pdps * taken(DamageType::Physical);
+        let fire = self.fire * taken(DamageType::Fire);
+        let cold = self.cold * taken(DamageType::Cold);
+        let lightning = self.lightning * taken(DamageType::Lightning);
+        let cdps = self.cdps * taken(DamageType::Chaos);
+        let edps = fire + cold + lightning;
+        let total = pdps + edps + cdps;
+        let crit_multiplier = match self.total > 0.0 {
+            true => self.total_with_crit / self.total,
+            false => 1.0,
+        };
+
+        Dps {
+            total,
+            edps,
+            pdps,
+            cdps,
+            fire,
+            cold,
+            lightning,
+            damages: self
+                .damages
+                .iter()
+                .map(|flat| {
+                    let taken = taken(flat.damage_type);
+                    FlatDamage {
+                        damage_type: flat.damage_type,
+                        range: Range(
+                            (flat.range.0 as f32 * taken).round() as u16,
+                            (flat.range.1 as f32 * taken).round() as u16,
+                        ),
+                    }
+                })
+                .collect(),
+            crit_chance: self.crit_chance,
+            total_with_crit: total * crit_multiplier,
+            caster: self.caster.clone(),
+        }
+    }
+}
+
+impl Weapon {
+    /// Character-adjusted DPS dealt to the enemy.
+    pub fn effective_dps(&self, enemy: &Enemy, profile: &BuildProfile) -> Dps {
+        self.dps_with_profile(profile).against(enemy, profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::ironwood_shortbow, Explicits, PhysModifier};
+
+    #[test]
+    fn lightning_loses_to_resistances() {
+        let weapon = |explicits: Explicits| Weapon {
+            explicits,
+            sockets: 1,
+            ..ironwood_shortbow()
+        };
+        let lightning = weapon(Explicits {
+            flats: vec![FlatDamage {
+                damage_type: DamageType::Lightning,
+                range: Range(5, 120),
+            }],
+            ..Default::default()
+        });
+        let phys = weapon(Explicits {
+            phys: Some(PhysModifier(170)),
+            ..Default::default()
+        });
+        let profile = BuildProfile::default();
+
+        let boss = Enemy::pinnacle_boss();
+        let lightning_dps = lightning.effective_dps(&boss, &profile);
+        assert!((lightning.dps().lightning * 0.5 - lightning_dps.lightning).abs() < 0.01);
+
+        let ratio = |weapon: &Weapon, enemy: &Enemy| {
+            weapon.effective_dps(enemy, &profile).total / weapon.dps().total
+        };
+        assert!(ratio(&lightning, &boss) < ratio(&lightning, &Enemy::normal_mob()));
+        assert!(ratio(&phys, &boss) < ratio(&phys, &Enemy::normal_mob()));
+
+        let penetration = BuildProfile {
+            lightning_penetration: 20,
+            ..Default::default()
+        };
+        assert!(lightning.effective_dps(&boss, &penetration).total > lightning_dps.total);
+        assert_eq!(0, boss.effective_resistance(DamageType::Lightning, 60));
+        assert_eq!(
+            -10,
+            Enemy {
+                exposure: 10,
+                ..Default::default()
+            }
+            .effective_resistance(DamageType::Cold, 20)
+        );
+    }
+}
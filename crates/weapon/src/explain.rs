@@ -0,0 +1,230 @@
+use crate::{DamageType, Range, Rune, Weapon};
+use serde::{Deserialize, Serialize};
+
+/// Every step of the DPS formula, as [`Weapon::dps`] computes it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DpsExplanation {
+    pub attack_speed: AttackSpeedStep,
+    /// Damage types the weapon deals.
+    pub damage_types: Vec<DamageTypeStep>,
+    pub total: f32,
+    pub crit: CritStep,
+    pub total_with_crit: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackSpeedStep {
+    pub base: f32,
+    /// % increased Attack Speed from modifiers.
+    pub modifiers: u16,
+    /// % increased Attack Speed from runes.
+    pub runes: u16,
+    pub attacks_per_second: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageTypeStep {
+    pub damage_type: DamageType,
+    pub base: Range,
+    /// Flat damage added on top of base damage, by source.
+    pub added: Vec<AddedDamage>,
+    /// Base and added damage.
+    pub flat: Range,
+    /// Quality, applies to physical damage only.
+    pub quality: u8,
+    /// Local % increased damage of the type from modifiers.
+    pub increased_modifiers: u16,
+    /// Local % increased damage of the type from runes.
+    pub increased_runes: u16,
+    /// Quality and increased damage together.
+    pub multiplier: f32,
+    /// Hit damage after local modifiers, rounded as on the tooltip.
+    pub hit: Range,
+    /// Average unrounded hit times attacks per second.
+    pub dps: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddedDamage {
+    pub source: DamageSource,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageSource {
+    Explicit,
+    Implicit,
+    Enchant,
+    Rune(Rune),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CritStep {
+    pub base_chance: f32,
+    /// Base chance with local modifiers and runes, capped at 100.
+    pub chance: f32,
+    pub damage_bonus: u16,
+    /// Average hit multiplier, weighted by chance.
+    pub multiplier: f32,
+}
+
+impl Weapon {
+    /// Step by step trace of the DPS computation, for comparing with the in-game tooltip.
+    pub fn explain(&self) -> DpsExplanation {
+        let attack_speed = AttackSpeedStep {
+            base: self.base_aps(),
+            modifiers: self
+                .modifiers()
+                .iter()
+                .map(|m| m.atk_spd.unwrap_or_default().0 as u16)
+                .sum(),
+            runes: self
+                .runes
+                .iter()
+                .filter_map(|r| r.attack_speed_martial())
+                .map(|atk_spd| atk_spd.0 as u16)
+                .sum(),
+            attacks_per_second: self.attacks_per_second(),
+        };
+
+        let damage_types: Vec<DamageTypeStep> = DamageType::ALL
+            .into_iter()
+            .map(|damage_type| self.explain_damage_type(damage_type))
+            .filter(|step| step.flat.sum() > 0)
+            .collect();
+        let total = damage_types.iter().map(|step| step.dps).sum();
+
+        let crit = CritStep {
+            base_chance: self.base_stats().crit,
+            chance: self.crit_chance(),
+            damage_bonus: self.crit_damage_bonus(),
+            multiplier: self.crit_multiplier(),
+        };
+
+        DpsExplanation {
+            attack_speed,
+            damage_types,
+            total,
+            total_with_crit: total * crit.multiplier,
+            crit,
+        }
+    }
+
+    fn explain_damage_type(&self, damage_type: DamageType) -> DamageTypeStep {
+        let of_type = |range: Range, flat_type: DamageType| match flat_type == damage_type {
+            true => range,
+            false => Range::default(),
+        };
+
+        let base = self
+            .base_damage()
+            .iter()
+            .map(|flat| of_type(flat.range, flat.damage_type))
+            .sum();
+
+        let mut added = vec![];
+        let modifiers = self.modifiers();
+        let sources = [
+            DamageSource::Explicit,
+            DamageSource::Implicit,
+            DamageSource::Enchant,
+        ];
+        for (modifiers, source) in modifiers.into_iter().zip(sources) {
+            for flat in modifiers.flats.iter() {
+                added.push(AddedDamage {
+                    source,
+                    range: of_type(flat.range, flat.damage_type),
+                });
+            }
+        }
+        for rune in &self.runes {
+            if let Some(flat) = rune.flat_martial() {
+                added.push(AddedDamage {
+                    source: DamageSource::Rune(*rune),
+                    range: of_type(flat.range, flat.damage_type),
+                });
+            }
+        }
+        added.retain(|added| added.range.sum() > 0);
+
+        let (quality, increased_modifiers, increased_runes) = match damage_type {
+            DamageType::Physical => (
+                self.quality.0,
+                self.modifiers()
+                    .iter()
+                    .map(|m| m.phys.unwrap_or_default().0)
+                    .sum(),
+                self.runes
+                    .iter()
+                    .filter_map(|r| r.phys_martial())
+                    .map(|phys| phys.0)
+                    .sum(),
+            ),
+            DamageType::Fire | DamageType::Cold | DamageType::Lightning => (
+                0,
+                0,
+                self.runes
+                    .iter()
+                    .filter_map(|r| r.elemental_martial())
+                    .map(|elemental| elemental.0)
+                    .sum(),
+            ),
+            DamageType::Chaos => (0, 0, 0),
+        };
+
+        DamageTypeStep {
+            damage_type,
+            base,
+            added,
+            flat: self.flat_damage(damage_type),
+            quality,
+            increased_modifiers,
+            increased_runes,
+            multiplier: self.local_multiplier(damage_type),
+            hit: self.damage_range(damage_type),
+            dps: self.damage_type_dps(damage_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::ironwood_shortbow, FlatDamage};
+
+    #[test]
+    fn explain_matches_dps() {
+        let mut weapon = ironwood_shortbow();
+        weapon.explicits.flats.push(FlatDamage {
+            damage_type: DamageType::Fire,
+            range: Range(10, 20),
+        });
+        weapon.runes = vec![
+            Rune::from_id("GreaterIron").unwrap(),
+            Rune::from_id("Desert").unwrap(),
+        ];
+
+        let explanation = weapon.explain();
+        let dps = weapon.dps();
+        assert!((dps.total - explanation.total).abs() < 0.01);
+        assert!((dps.total_with_crit - explanation.total_with_crit).abs() < 0.01);
+
+        let phys = &explanation.damage_types[0];
+        assert_eq!(DamageType::Physical, phys.damage_type);
+        assert_eq!(20, phys.quality);
+        assert_eq!(118, phys.increased_modifiers);
+        assert_eq!(18, phys.increased_runes);
+
+        let fire = &explanation.damage_types[1];
+        assert_eq!(
+            vec![
+                DamageSource::Explicit,
+                DamageSource::Rune(Rune::from_id("Desert").unwrap())
+            ],
+            fire.added.iter().map(|a| a.source).collect::<Vec<_>>()
+        );
+        assert_eq!(fire.flat, fire.added.iter().map(|a| a.range).sum());
+        assert!(serde_json::to_string(&explanation).is_ok());
+    }
+}
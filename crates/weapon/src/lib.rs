@@ -1,20 +1,88 @@
+pub mod affixes;
+pub mod caster;
+pub mod corrupt;
+pub mod craft;
+pub mod divine;
+pub mod dual;
+pub mod enemy;
+pub mod explain;
+pub mod mods;
+pub mod optimize;
+pub mod perfect;
+pub mod price;
+pub mod profile;
+pub mod quality;
+pub mod rune;
+pub mod skill;
+pub mod tooltip;
+
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::ops::Add;
 
+pub use affixes::{AffixAnalysis, OpenSlot};
+pub use caster::{
+    CastSpeedModifier, CasterExplicits, CasterScore, IncreasedDamage, SpellDamageModifier,
+    SpellLevels,
+};
+pub use corrupt::{
+    CorruptionChance, CorruptionEnchant, CorruptionKind, CorruptionOutcome, CorruptionTable,
+    CORRUPTION, CORRUPTION_QUALITY_CHANGE, MAX_CORRUPTED_QUALITY,
+};
+pub use craft::{
+    simulate_crafting, CraftError, CraftItem, CraftOutcome, Currency, Rarity, RolledMod,
+    UNTIERED_MOD_ID,
+};
+pub use divine::DivineOutcome;
+pub use dual::{DualWield, DualWieldError, DualWieldRunes};
+pub use enemy::{Enemy, MAX_ARMOUR_REDUCTION, MAX_ENEMY_RESISTANCE};
+pub use explain::{
+    AddedDamage, AttackSpeedStep, CritStep, DamageSource, DamageTypeStep, DpsExplanation,
+};
+pub use mods::{
+    Affix, ModStat, ModStats, ModTier, ModTierInfo, RollRange, MAX_ITEM_LEVEL, MOD_DATA_SOURCED,
+    MOD_STATS,
+};
+pub use optimize::RuneConstraints;
+pub use perfect::PerfectWeapon;
+pub use price::{PriceError, PriceTable, RunePurchase, MIN_COST, RUNES_PER_UPGRADE};
+pub use profile::{BuildProfile, Conversion, ProfileError};
+pub use quality::{QualityStep, MAX_QUALITY};
+pub use rune::{
+    Condition, ConditionalBonus, Rune, RuneCasterBonus, RuneMartialBonus, RuneStats, RuneTier,
+    SocketableKind, SocketedBonus, RUNE_STATS,
+};
+pub use skill::{SkillStats, SKILL_STATS};
+pub use tooltip::{Tooltip, PLACEHOLDER_ITEM_NAME, SECTION_SEPARATOR};
+
 pub static WEAPON_STATS: Lazy<Vec<WeaponStats>> =
     Lazy::new(|| serde_json::from_str(include_str!("../data/bases.json")).unwrap());
 
+/// Critical Damage Bonus every hit gets on top of explicit modifiers, in percents.
+pub const BASE_CRIT_DAMAGE_BONUS: u16 = 100;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weapon {
     pub base: String,
     pub item_class: ItemClass,
     pub quality: Quality,
     pub explicits: Explicits,
+    /// Implicit modifiers, including base implicits.
+    pub implicits: Explicits,
+    /// Enchants, e.g. from corruption.
+    pub enchants: Explicits,
     pub runes: Vec<Rune>,
+    pub sockets: u8,
+    /// Item level, gates modifier tiers.
+    pub item_level: Option<u8>,
 }
 
 impl Weapon {
+    /// Explicit, implicit and enchant modifiers of the item.
+    pub fn modifiers(&self) -> [&Explicits; 3] {
+        [&self.explicits, &self.implicits, &self.enchants]
+    }
+
     pub fn get_all_weapons_stats() -> &'static Vec<WeaponStats> {
         &WEAPON_STATS
     }
@@ -24,67 +92,108 @@ impl Weapon {
     }
 
     pub fn handed(&self) -> Handed {
-        match self.item_class {
-            ItemClass::OneHandMaces => Handed::OneHanded,
-            ItemClass::TwoHandMaces => Handed::TwoHanded,
-            ItemClass::Quarterstaves => Handed::TwoHanded,
-            ItemClass::Bows => Handed::TwoHanded,
-            ItemClass::Crossbows => Handed::TwoHanded,
-            ItemClass::Spears => Handed::OneHanded,
-            ItemClass::Talismans => Handed::TwoHanded,
+        self.item_class.handed()
+    }
+
+    /// Same weapon with socketed runes removed.
+    pub fn unsocketed(&self) -> Weapon {
+        Weapon {
+            runes: vec![],
+            ..self.clone()
         }
     }
 
     pub fn dps(&self) -> Dps {
         let pdps = self.phys_dps();
-        let edps = self.elemental_dps();
+        let fire = self.fire_dps();
+        let cold = self.cold_dps();
+        let lightning = self.lightning_dps();
+        let edps = fire + cold + lightning;
         let cdps = self.chaos_dps();
         let total = pdps + edps + cdps;
+        let crit_chance = self.crit_chance();
 
         Dps {
             total,
             edps,
             pdps,
             cdps,
+            fire,
+            cold,
+            lightning,
+            damages: self.damage_ranges(),
+            crit_chance,
+            total_with_crit: total * self.crit_multiplier(),
+            caster: self.caster_score(),
         }
     }
 
+    /// Critical hit chance in percents: base chance plus local modifiers and socketables, capped at 100.
+    pub fn crit_chance(&self) -> f32 {
+        let explicit: f32 = self
+            .modifiers()
+            .iter()
+            .map(|m| m.crit_chance.unwrap_or_default().0)
+            .sum();
+        let runes: f32 = self
+            .runes
+            .iter()
+            .filter_map(|r| r.crit_chance_martial())
+            .map(|crit_chance| crit_chance.0)
+            .sum();
+        (self.base_stats().crit + explicit + runes).min(100.0)
+    }
+
+    /// Critical Damage Bonus in percents, including [`BASE_CRIT_DAMAGE_BONUS`].
+    pub fn crit_damage_bonus(&self) -> u16 {
+        let runes: u16 = self
+            .runes
+            .iter()
+            .filter_map(|r| r.crit_damage_martial())
+            .map(|crit_dmg| crit_dmg.0)
+            .sum();
+        let explicit: u16 = self
+            .modifiers()
+            .iter()
+            .map(|m| m.crit_dmg.unwrap_or_default().0)
+            .sum();
+        BASE_CRIT_DAMAGE_BONUS + explicit + runes
+    }
+
+    /// Average damage multiplier of a hit, weighted by critical hit chance.
+    pub fn crit_multiplier(&self) -> f32 {
+        1.0 + self.crit_chance() / 100.0 * self.crit_damage_bonus() as f32 / 100.0
+    }
+
     pub fn with_different_runes(&self) -> Vec<DpsWithRunes> {
-        let runes = Rune::runes();
-        let mut vec: Vec<DpsWithRunes> = vec![];
-
-        match self.handed() {
-            Handed::OneHanded => {
-                for rune in Rune::runes() {
-                    let mut weapon_with_runes = self.clone();
-                    weapon_with_runes.runes = vec![rune];
-                    vec.push(DpsWithRunes {
-                        runes: vec![rune],
-                        dps: weapon_with_runes.dps(),
-                    });
-                }
-            }
-            Handed::TwoHanded => {
-                // Iterate over all pairs, including same rune pairs, but ensure order doesn't matter
-                for i in 0..runes.len() {
-                    for j in i..runes.len() {
-                        // Start j from i to avoid reverse pairs like (Storm, Iron)
-                        let rune1 = runes[i];
-                        let rune2 = runes[j];
-
-                        let mut weapon_with_runes = self.clone();
-                        weapon_with_runes.runes = vec![rune1, rune2];
-
-                        vec.push(DpsWithRunes {
-                            runes: vec![rune1, rune2],
-                            dps: weapon_with_runes.dps(),
-                        });
-                    }
-                }
-            }
-        }
+        self.with_different_runes_with_profile(&BuildProfile::default())
+    }
 
-        vec.sort_by(|a, b| b.dps.total.partial_cmp(&a.dps.total).unwrap());
+    /// The weapon with every filling of sockets, including partially filled ones.
+    pub fn rune_fills(&self) -> impl Iterator<Item = Weapon> + '_ {
+        let runes = match self.is_caster() {
+            true => Rune::caster_runes(),
+            false => Rune::martial_runes(),
+        };
+        let unsocketed = self.unsocketed();
+
+        (1..=self.sockets as usize)
+            .flat_map(move |size| Rune::combinations(&runes, size))
+            .map(move |runes| Weapon {
+                runes,
+                ..unsocketed.clone()
+            })
+    }
+
+    /// Same as [`Weapon::with_different_runes`], but sorted by DPS of a single damage type.
+    pub fn with_different_runes_by(&self, damage_type: DamageType) -> Vec<DpsWithRunes> {
+        let mut vec = self.with_different_runes();
+        vec.sort_by(|a, b| {
+            b.dps
+                .of_type(damage_type)
+                .partial_cmp(&a.dps.of_type(damage_type))
+                .unwrap()
+        });
 
         vec
     }
@@ -106,99 +215,142 @@ impl Weapon {
             .as_ref()
     }
 
-    pub fn phys_dps(&self) -> f32 {
-        let flat_phys = self
-            .explicits
-            .flats
+    /// Base attacks per second with local attack speed from modifiers and socketables applied.
+    pub fn attacks_per_second(&self) -> f32 {
+        let runes: u16 = self
+            .runes
             .iter()
-            .find(|flat| matches!(flat.damage_type, DamageType::Physical))
-            .cloned();
-
-        let base_phys = self
-            .base_damage()
+            .filter_map(|r| r.attack_speed_martial())
+            .map(|atk_spd| atk_spd.0 as u16)
+            .sum();
+        let explicit: u16 = self
+            .modifiers()
             .iter()
-            .find(|damage| matches!(damage.damage_type, DamageType::Physical))
-            .copied()
-            .unwrap_or_default();
-
-        let runes_phys_modifier = PhysModifier(
-            self.runes
-                .iter()
-                .filter(|rune| rune.is_iron())
-                .map(|r| match r.martial() {
-                    RuneMartialBonus::Phys(phys_modifier) => phys_modifier.0,
-                    RuneMartialBonus::Flat(_) => 0,
-                })
-                .sum(),
-        );
-
-        // let qual = 1.0 + self.quality.0 as f32 / 100.;
-        // let flat = (base_phys.range + flat_phys_explicit.range) as f32;
-        // let percent = 1.0 + (phys_modifier_explicit.0 + runes_phys_modifier.0) as f32 / 100.;
-
-        // println!("{qual} {flat} {percent}");
+            .map(|m| m.atk_spd.unwrap_or_default().0 as u16)
+            .sum();
+        let atk_spd = explicit + runes;
 
-        (1.0 + self.quality.0 as f32 / 100.0)
-            * (base_phys.range.sum() + flat_phys.unwrap_or_default().range.sum()) as f32
-            * (self.base_aps() * (1.0 + self.explicits.atk_spd.unwrap_or_default().0 as f32 / 100.))
-            * (1.0
-                + (self.explicits.phys.unwrap_or_default().0 + runes_phys_modifier.0) as f32 / 100.)
-            * 0.5
+        self.base_aps() * (1.0 + atk_spd as f32 / 100.)
     }
 
-    pub fn elemental_dps(&self) -> f32 {
-        let flat_elemental_explicits_sum: Range = self
-            .explicits
-            .flats
+    /// Sum of base, explicit, implicit, enchant and rune flat damage of given type, before local modifiers.
+    pub fn flat_damage(&self, damage_type: DamageType) -> Range {
+        let base: Range = self
+            .base_damage()
             .iter()
-            .filter(|flat| flat.is_elemental())
+            .filter(|flat| flat.damage_type == damage_type)
             .map(|flat| flat.range)
             .sum();
 
-        let base_elemental_damage: Range = self
-            .base_damage()
+        let explicits: Range = self
+            .modifiers()
             .iter()
-            .filter(|flat| flat.is_elemental())
+            .flat_map(|m| m.flats.iter())
+            .filter(|flat| flat.damage_type == damage_type)
             .map(|flat| flat.range)
             .sum();
 
-        let runes_elemental_damage: Range = self
+        let runes: Range = self
             .runes
             .iter()
             .filter_map(|rune| rune.flat_martial())
-            .filter(|flat| flat.is_elemental())
+            .filter(|flat| flat.damage_type == damage_type)
             .map(|flat| flat.range)
             .sum();
 
-        (base_elemental_damage.sum()
-            + flat_elemental_explicits_sum.sum()
-            + runes_elemental_damage.sum()) as f32
-            * (self.base_aps() * (1.0 + self.explicits.atk_spd.unwrap_or_default().0 as f32 / 100.))
-            * 0.5
+        base + explicits + runes
+    }
+
+    /// Damage multiplier of given type. Quality and % increased Physical Damage
+    /// only apply to physical damage, socketed % increased Elemental Damage with Attacks
+    /// only to elemental.
+    pub fn local_multiplier(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => {
+                let runes_phys_modifier: u16 = self
+                    .runes
+                    .iter()
+                    .filter_map(|r| r.phys_martial())
+                    .map(|phys_modifier| phys_modifier.0)
+                    .sum();
+
+                let explicit_phys_modifier: u16 = self
+                    .modifiers()
+                    .iter()
+                    .map(|m| m.phys.unwrap_or_default().0)
+                    .sum();
+
+                (1.0 + self.quality.0 as f32 / 100.0)
+                    * (1.0 + (explicit_phys_modifier + runes_phys_modifier) as f32 / 100.)
+            }
+            DamageType::Fire | DamageType::Cold | DamageType::Lightning => {
+                let runes_elemental_modifier: u16 = self
+                    .runes
+                    .iter()
+                    .filter_map(|r| r.elemental_martial())
+                    .map(|elemental| elemental.0)
+                    .sum();
+
+                1.0 + runes_elemental_modifier as f32 / 100.
+            }
+            DamageType::Chaos => 1.0,
+        }
     }
 
-    pub fn chaos_dps(&self) -> f32 {
-        let base_chaos_damage: Range = self
-            .base_damage()
-            .iter()
-            .filter(|flat| flat.is_chaos())
-            .map(|flat| flat.range)
-            .sum();
+    /// Hit damage range of given type after local modifiers, rounded to whole numbers.
+    pub fn damage_range(&self, damage_type: DamageType) -> Range {
+        let flat = self.flat_damage(damage_type);
+        let multiplier = self.local_multiplier(damage_type);
 
-        let chaos_explicit = self
-            .explicits
-            .flats
-            .iter()
-            .find(|f| f.is_chaos())
-            .copied()
-            .unwrap_or_default()
-            .range;
+        Range(
+            (flat.0 as f32 * multiplier).round() as u16,
+            (flat.1 as f32 * multiplier).round() as u16,
+        )
+    }
 
-        (base_chaos_damage.sum() + chaos_explicit.sum()) as f32
-            * (self.base_aps() * (1.0 + self.explicits.atk_spd.unwrap_or_default().0 as f32 / 100.))
+    /// Hit damage ranges of every damage type the weapon deals.
+    pub fn damage_ranges(&self) -> Vec<FlatDamage> {
+        DamageType::ALL
+            .into_iter()
+            .map(|damage_type| FlatDamage {
+                damage_type,
+                range: self.damage_range(damage_type),
+            })
+            .filter(|flat| flat.range.sum() > 0)
+            .collect()
+    }
+
+    pub fn damage_type_dps(&self, damage_type: DamageType) -> f32 {
+        self.flat_damage(damage_type).sum() as f32
+            * self.local_multiplier(damage_type)
+            * self.attacks_per_second()
             * 0.5
     }
 
+    pub fn phys_dps(&self) -> f32 {
+        self.damage_type_dps(DamageType::Physical)
+    }
+
+    pub fn fire_dps(&self) -> f32 {
+        self.damage_type_dps(DamageType::Fire)
+    }
+
+    pub fn cold_dps(&self) -> f32 {
+        self.damage_type_dps(DamageType::Cold)
+    }
+
+    pub fn lightning_dps(&self) -> f32 {
+        self.damage_type_dps(DamageType::Lightning)
+    }
+
+    pub fn elemental_dps(&self) -> f32 {
+        self.fire_dps() + self.cold_dps() + self.lightning_dps()
+    }
+
+    pub fn chaos_dps(&self) -> f32 {
+        self.damage_type_dps(DamageType::Chaos)
+    }
+
     pub fn total(&self) -> f32 {
         self.phys_dps() + self.elemental_dps() + self.chaos_dps()
     }
@@ -211,6 +363,11 @@ pub struct WeaponStats {
     pub img: String,
     pub damages: Vec<FlatDamage>,
     pub aps: f32,
+    /// Base critical hit chance, in percents.
+    pub crit: f32,
+    /// Base implicit modifiers, as displayed on the item.
+    #[serde(default)]
+    pub implicits: Vec<String>,
 }
 
 pub fn add(left: u64, right: u64) -> u64 {
@@ -248,173 +405,54 @@ impl std::iter::Sum for Range {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Rune {
-    LesserIron,
-    Iron,
-    GreaterIron,
-    LesserDesert,
-    Desert,
-    GreaterDesert,
-    LesserGlacial,
-    Glacial,
-    GreaterGlacial,
-    LesserStorm,
-    Storm,
-    GreaterStorm,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum RuneMartialBonus {
-    Phys(PhysModifier),
-    Flat(FlatDamage),
-}
-
-impl Rune {
-    pub fn runes() -> [Rune; 12] {
-        [
-            Rune::LesserIron,
-            Rune::Iron,
-            Rune::GreaterIron,
-            Rune::LesserDesert,
-            Rune::Desert,
-            Rune::GreaterDesert,
-            Rune::LesserGlacial,
-            Rune::Glacial,
-            Rune::GreaterGlacial,
-            Rune::LesserStorm,
-            Rune::Storm,
-            Rune::GreaterStorm,
-        ]
-    }
-
-    pub fn is_iron(&self) -> bool {
-        matches!(self, Rune::LesserIron | Rune::Iron | Rune::GreaterIron)
-    }
-
-    pub fn martial(&self) -> RuneMartialBonus {
-        match self {
-            Rune::LesserIron => RuneMartialBonus::Phys(Rune::lesser_iron_rune_martial()),
-            Rune::Iron => RuneMartialBonus::Phys(Rune::iron_rune_martial()),
-            Rune::GreaterIron => RuneMartialBonus::Phys(Rune::greater_iron_rune_martial()),
-            Rune::LesserDesert => RuneMartialBonus::Flat(Rune::lesser_desert_rune_martial()),
-            Rune::Desert => RuneMartialBonus::Flat(Rune::desert_rune_martial()),
-            Rune::GreaterDesert => RuneMartialBonus::Flat(Rune::greater_desert_rune_martial()),
-            Rune::LesserGlacial => RuneMartialBonus::Flat(Rune::lesser_glacial_rune_martial()),
-            Rune::Glacial => RuneMartialBonus::Flat(Rune::glacial_rune_martial()),
-            Rune::GreaterGlacial => RuneMartialBonus::Flat(Rune::greater_glacial_rune_martial()),
-            Rune::LesserStorm => RuneMartialBonus::Flat(Rune::lesser_storm_rune_martial()),
-            Rune::Storm => RuneMartialBonus::Flat(Rune::storm_rune_martial()),
-            Rune::GreaterStorm => RuneMartialBonus::Flat(Rune::greater_storm_rune_martial()),
-        }
-    }
-
-    pub fn flat_martial(&self) -> Option<FlatDamage> {
-        match self {
-            Rune::LesserIron => None,
-            Rune::Iron => None,
-            Rune::GreaterIron => None,
-            Rune::LesserDesert => Some(Rune::lesser_desert_rune_martial()),
-            Rune::Desert => Some(Rune::desert_rune_martial()),
-            Rune::GreaterDesert => Some(Rune::greater_desert_rune_martial()),
-            Rune::LesserGlacial => Some(Rune::lesser_glacial_rune_martial()),
-            Rune::Glacial => Some(Rune::glacial_rune_martial()),
-            Rune::GreaterGlacial => Some(Rune::greater_glacial_rune_martial()),
-            Rune::LesserStorm => Some(Rune::lesser_storm_rune_martial()),
-            Rune::Storm => Some(Rune::storm_rune_martial()),
-            Rune::GreaterStorm => Some(Rune::greater_storm_rune_martial()),
-        }
-    }
-
-    pub fn lesser_desert_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Fire,
-            range: Range(4, 6),
-        }
-    }
-
-    pub fn desert_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Fire,
-            range: Range(7, 11),
-        }
-    }
-
-    pub fn greater_desert_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Fire,
-            range: Range(13, 16),
-        }
-    }
-
-    pub fn lesser_glacial_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Cold,
-            range: Range(3, 5),
-        }
-    }
-
-    pub fn glacial_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Cold,
-            range: Range(6, 10),
-        }
-    }
-
-    pub fn greater_glacial_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Cold,
-            range: Range(9, 15),
-        }
-    }
-
-    pub fn lesser_storm_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Lightning,
-            range: Range(1, 10),
-        }
-    }
-
-    pub fn storm_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Lightning,
-            range: Range(1, 20),
-        }
-    }
-
-    pub fn greater_storm_rune_martial() -> FlatDamage {
-        FlatDamage {
-            damage_type: DamageType::Lightning,
-            range: Range(1, 30),
-        }
-    }
-
-    pub fn lesser_iron_rune_martial() -> PhysModifier {
-        PhysModifier(14)
-    }
-
-    pub fn iron_rune_martial() -> PhysModifier {
-        PhysModifier(16)
-    }
-
-    pub fn greater_iron_rune_martial() -> PhysModifier {
-        PhysModifier(18)
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Default, Deserialize, PartialEq)]
 pub struct Explicits {
     pub flats: Vec<FlatDamage>,
     pub phys: Option<PhysModifier>,
     pub atk_spd: Option<AttackSpeedModifier>,
+    pub crit_chance: Option<CritChanceModifier>,
+    pub crit_dmg: Option<CritDamageModifier>,
+    /// Spell modifiers. Present only for caster weapons.
+    pub caster: Option<CasterExplicits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dps {
+    /// Damage per second without critical hits.
     pub total: f32,
     pub edps: f32,
     pub pdps: f32,
     pub cdps: f32,
+    pub fire: f32,
+    pub cold: f32,
+    pub lightning: f32,
+    /// Hit damage ranges per damage type, after local modifiers.
+    pub damages: Vec<FlatDamage>,
+    pub crit_chance: f32,
+    /// Expected damage per second with critical hits weighted in.
+    pub total_with_crit: f32,
+    /// Spell evaluation of caster weapons.
+    pub caster: Option<CasterScore>,
+}
+
+impl Dps {
+    /// Value weapons are ranked by: caster score for caster weapons, total DPS with crit otherwise.
+    pub fn score(&self) -> f32 {
+        match &self.caster {
+            Some(caster) => caster.score,
+            None => self.total_with_crit,
+        }
+    }
+
+    pub fn of_type(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.pdps,
+            DamageType::Fire => self.fire,
+            DamageType::Cold => self.cold,
+            DamageType::Lightning => self.lightning,
+            DamageType::Chaos => self.cdps,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -428,6 +466,16 @@ pub enum Handed {
     TwoHanded,
 }
 
+impl Handed {
+    /// Sockets of a not corrupted weapon.
+    pub fn default_sockets(&self) -> u8 {
+        match self {
+            Handed::OneHanded => 1,
+            Handed::TwoHanded => 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ItemClass {
@@ -445,6 +493,57 @@ pub enum ItemClass {
     Spears,
     #[serde(rename = "Talismans")]
     Talismans,
+    #[serde(rename = "One Hand Swords")]
+    OneHandSwords,
+    #[serde(rename = "Two Hand Swords")]
+    TwoHandSwords,
+    #[serde(rename = "One Hand Axes")]
+    OneHandAxes,
+    #[serde(rename = "Two Hand Axes")]
+    TwoHandAxes,
+    #[serde(rename = "Daggers")]
+    Daggers,
+    #[serde(rename = "Claws")]
+    Claws,
+    #[serde(rename = "Flails")]
+    Flails,
+    #[serde(rename = "Wands")]
+    Wands,
+    #[serde(rename = "Staves")]
+    Staves,
+    #[serde(rename = "Sceptres")]
+    Sceptres,
+}
+
+impl ItemClass {
+    pub fn handed(&self) -> Handed {
+        match self {
+            ItemClass::OneHandMaces => Handed::OneHanded,
+            ItemClass::TwoHandMaces => Handed::TwoHanded,
+            ItemClass::Quarterstaves => Handed::TwoHanded,
+            ItemClass::Bows => Handed::TwoHanded,
+            ItemClass::Crossbows => Handed::TwoHanded,
+            ItemClass::Spears => Handed::OneHanded,
+            ItemClass::Talismans => Handed::TwoHanded,
+            ItemClass::OneHandSwords => Handed::OneHanded,
+            ItemClass::TwoHandSwords => Handed::TwoHanded,
+            ItemClass::OneHandAxes => Handed::OneHanded,
+            ItemClass::TwoHandAxes => Handed::TwoHanded,
+            ItemClass::Daggers => Handed::OneHanded,
+            ItemClass::Claws => Handed::OneHanded,
+            ItemClass::Flails => Handed::OneHanded,
+            ItemClass::Wands => Handed::OneHanded,
+            ItemClass::Staves => Handed::TwoHanded,
+            ItemClass::Sceptres => Handed::OneHanded,
+        }
+    }
+
+    pub fn is_caster(&self) -> bool {
+        matches!(
+            self,
+            ItemClass::Wands | ItemClass::Staves | ItemClass::Sceptres
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
@@ -459,6 +558,21 @@ pub struct PhysModifier(pub u16);
 #[serde(transparent)]
 pub struct Quality(pub u8);
 
+/// X% increased Elemental Damage with Attacks
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ElementalDamageModifier(pub u16);
+
+/// Local +X% to Critical Hit Chance
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CritChanceModifier(pub f32);
+
+/// +X% to Critical Damage Bonus
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CritDamageModifier(pub u16);
+
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct FlatDamage {
     pub damage_type: DamageType,
@@ -475,7 +589,7 @@ impl FlatDamage {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum DamageType {
     #[default]
     #[serde(rename = "physical", alias = "phys")]
@@ -491,6 +605,34 @@ pub enum DamageType {
 }
 
 impl DamageType {
+    pub const ALL: [DamageType; 5] = [
+        DamageType::Physical,
+        DamageType::Fire,
+        DamageType::Cold,
+        DamageType::Lightning,
+        DamageType::Chaos,
+    ];
+
+    /// Order damage is converted in. Damage converts only to types later in the order.
+    pub const CONVERSION_ORDER: [DamageType; 5] = [
+        DamageType::Physical,
+        DamageType::Lightning,
+        DamageType::Cold,
+        DamageType::Fire,
+        DamageType::Chaos,
+    ];
+
+    /// Position in [`DamageType::CONVERSION_ORDER`].
+    pub fn conversion_step(&self) -> usize {
+        match self {
+            DamageType::Physical => 0,
+            DamageType::Lightning => 1,
+            DamageType::Cold => 2,
+            DamageType::Fire => 3,
+            DamageType::Chaos => 4,
+        }
+    }
+
     pub fn is_elemental(&self) -> bool {
         match self {
             DamageType::Physical => false,
@@ -504,12 +646,41 @@ impl DamageType {
     pub fn is_chaos(&self) -> bool {
         matches!(self, DamageType::Chaos)
     }
+
+    /// Name as displayed on items, e.g. "Fire" in "Adds 7 to 16 Fire Damage".
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "Physical",
+            DamageType::Fire => "Fire",
+            DamageType::Cold => "Cold",
+            DamageType::Lightning => "Lightning",
+            DamageType::Chaos => "Chaos",
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Rare bow with 118% increased Physical Damage, shared by the tests of all modules.
+    pub(crate) fn ironwood_shortbow() -> Weapon {
+        Weapon {
+            base: "Ironwood Shortbow".to_owned(),
+            item_class: ItemClass::Bows,
+            quality: Quality(20),
+            explicits: Explicits {
+                phys: Some(PhysModifier(118)),
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 2,
+            item_level: Some(75),
+        }
+    }
+
     // #[test]
     // fn phys_dps() {
     //     let white_weapon = Weapon {
@@ -558,12 +729,63 @@ mod tests {
             item_class: ItemClass::OneHandMaces,
             quality: Quality(0),
             explicits: Explicits::default(),
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
             runes: vec![],
+            sockets: 1,
+            item_level: None,
         };
 
         assert_eq!(1, weapon.with_different_runes()[0].runes.len());
     }
 
+    #[test]
+    fn implicits_and_enchants_add_damage() {
+        let mut weapon = Weapon {
+            base: "Warpick".to_owned(),
+            item_class: ItemClass::OneHandMaces,
+            quality: Quality(0),
+            explicits: Explicits::default(),
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 1,
+            item_level: None,
+        };
+        let before = weapon.dps();
+
+        weapon.enchants.phys = Some(PhysModifier(20));
+        weapon.implicits.flats.push(FlatDamage {
+            damage_type: DamageType::Cold,
+            range: Range(5, 10),
+        });
+        let after = weapon.dps();
+
+        assert_eq!(one_fr_f32(before.pdps * 1.2), one_fr_f32(after.pdps));
+        assert_eq!(Range(5, 10), weapon.damage_range(DamageType::Cold));
+    }
+
+    #[test]
+    fn corrupted_extra_socket() {
+        let weapon = Weapon {
+            base: "Warpick".to_owned(),
+            item_class: ItemClass::OneHandMaces,
+            quality: Quality(0),
+            explicits: Explicits::default(),
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 2,
+            item_level: None,
+        };
+
+        let runes = weapon.with_different_runes();
+        assert_eq!(2, runes[0].runes.len());
+        assert_eq!(weapon.dps().total, weapon.unsocketed().dps().total);
+        // One rune combinations are included as well
+        assert!(runes.iter().any(|r| r.runes.len() == 1));
+    }
+
     fn one_fr_f32(val: f32) -> f32 {
         (val * 10.0).round() / 10.0
     }
@@ -587,7 +809,11 @@ mod tests {
                 ],
                 ..Default::default()
             },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
             runes: vec![],
+            sockets: 2,
+            item_level: None,
         };
 
         assert_eq!(97.6, one_fr_f32(cultist_bow.dps().total));
@@ -595,4 +821,105 @@ mod tests {
         assert_eq!(15.6, one_fr_f32(cultist_bow.chaos_dps()));
         assert_eq!(40.2, one_fr_f32(cultist_bow.elemental_dps()));
     }
+
+    #[test]
+    fn per_element_dps() {
+        let weapon = Weapon {
+            base: "Cultist Bow".to_owned(),
+            item_class: ItemClass::Bows,
+            quality: Quality(20),
+            explicits: Explicits {
+                flats: vec![FlatDamage {
+                    damage_type: DamageType::Fire,
+                    range: Range(5, 10),
+                }],
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![Rune::from_id("GreaterStorm").unwrap()],
+            sockets: 2,
+            item_level: None,
+        };
+
+        let dps = weapon.dps();
+        assert_eq!(Range(1, 30), weapon.damage_range(DamageType::Lightning));
+        assert_eq!(one_fr_f32(dps.edps), one_fr_f32(dps.fire + dps.lightning));
+        assert_eq!(0.0, dps.cold);
+        assert_eq!(
+            Rune::from_id("GreaterStorm").unwrap(),
+            weapon.with_different_runes_by(DamageType::Lightning)[0].runes[0]
+        );
+    }
+
+    #[test]
+    fn soul_cores_feed_dps() {
+        let mut weapon = Weapon {
+            base: "Leaden Greathammer".to_owned(),
+            item_class: ItemClass::TwoHandMaces,
+            quality: Quality(0),
+            explicits: Explicits {
+                flats: vec![FlatDamage {
+                    damage_type: DamageType::Fire,
+                    range: Range(7, 16),
+                }],
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 2,
+            item_level: None,
+        };
+        let before = weapon.dps();
+
+        weapon.runes = vec![
+            Rune::from_id("SoulCoreOfQuipolatl").unwrap(),
+            Rune::from_id("SoulCoreOfCitaqualotl").unwrap(),
+        ];
+        let after = weapon.dps();
+
+        assert_eq!(one_fr_f32(before.pdps * 1.05), one_fr_f32(after.pdps));
+        assert_eq!(one_fr_f32(before.fire * 1.05 * 1.3), one_fr_f32(after.fire));
+        assert!(weapon
+            .with_different_runes()
+            .iter()
+            .any(|r| r.runes.iter().any(|r| r.kind() == SocketableKind::SoulCore)));
+
+        // Crit Soul Cores raise the score, though not the non-crit total.
+        weapon.runes = vec![];
+        let hayoxi = Weapon {
+            runes: vec![Rune::from_id("SoulCoreOfHayoxi").unwrap()],
+            ..weapon.clone()
+        };
+        assert_eq!(weapon.dps().total, hayoxi.dps().total);
+        assert!(hayoxi.dps().score() > weapon.dps().score());
+    }
+
+    #[test]
+    fn crit_weighted_dps() {
+        let weapon = Weapon {
+            base: "Leaden Greathammer".to_owned(),
+            item_class: ItemClass::TwoHandMaces,
+            quality: Quality(0),
+            explicits: Explicits {
+                crit_chance: Some(CritChanceModifier(2.5)),
+                crit_dmg: Some(CritDamageModifier(16)),
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 2,
+            item_level: None,
+        };
+
+        let dps = weapon.dps();
+        assert_eq!(7.5, weapon.crit_chance());
+        assert_eq!(116, weapon.crit_damage_bonus());
+        assert_eq!(
+            one_fr_f32(dps.total * 1.087),
+            one_fr_f32(dps.total_with_crit)
+        );
+    }
 }
@@ -0,0 +1,349 @@
+use crate::{DamageType, Explicits, ItemClass, RolledMod, Weapon};
+use fastrand::Rng;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+pub static MOD_STATS: Lazy<Vec<ModStats>> =
+    Lazy::new(|| serde_json::from_str(include_str!("../data/mods.json")).unwrap());
+
+/// Whether mods.json is taken from game data. It is hand-entered, with placeholder
+/// spawn weights, see `data/README.md`.
+pub const MOD_DATA_SOURCED: bool = false;
+
+/// Highest item level modifiers are gated by.
+pub const MAX_ITEM_LEVEL: u8 = 82;
+
+/// Explicit modifier with its tiers, as in mods.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModStats {
+    pub id: String,
+    pub stat: ModStat,
+    pub affix: Affix,
+    /// Modifier line with values replaced by #, e.g. "Adds # to # Fire Damage".
+    pub text: String,
+    pub item_classes: Vec<ItemClass>,
+    /// Ordered from the lowest to the highest tier.
+    pub tiers: Vec<ModTier>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModStat {
+    Phys,
+    Flat(DamageType),
+    AttackSpeed,
+    CritChance,
+    CritDamage,
+    SpellLevels,
+    SpellDamage,
+    /// % increased damage of a type, on caster weapons.
+    Increased(DamageType),
+    CastSpeed,
+    /// Modifier which does not affect damage, e.g. accuracy or attributes.
+    Other,
+}
+
+impl ModStat {
+    /// Damage modifiers are prefixes, speed, level and critical hit modifiers are suffixes.
+    /// None for other modifiers, which can be either.
+    pub fn affix(&self) -> Option<Affix> {
+        match self {
+            ModStat::Phys | ModStat::Flat(_) | ModStat::SpellDamage | ModStat::Increased(_) => {
+                Some(Affix::Prefix)
+            }
+            ModStat::AttackSpeed
+            | ModStat::CritChance
+            | ModStat::CritDamage
+            | ModStat::SpellLevels
+            | ModStat::CastSpeed => Some(Affix::Suffix),
+            ModStat::Other => None,
+        }
+    }
+
+    /// Smallest difference between two rolls.
+    pub fn step(&self) -> f32 {
+        match self {
+            ModStat::CritChance => 0.01,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Affix {
+    Prefix,
+    Suffix,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModTier {
+    pub name: String,
+    /// Minimum item level.
+    pub level: u8,
+    pub weight: u32,
+    /// Roll range of every value of the modifier. Flat damage has two: minimum and maximum damage.
+    pub rolls: Vec<RollRange>,
+}
+
+/// Inclusive roll range of a modifier value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RollRange(pub f32, pub f32);
+
+impl RollRange {
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.0 - f32::EPSILON && value <= self.1 + f32::EPSILON
+    }
+
+    /// Position of value inside the range, from 0.0 (worst) to 1.0 (perfect).
+    pub fn quality(&self, value: f32) -> f32 {
+        if self.1 <= self.0 {
+            return 1.0;
+        }
+
+        ((value - self.0) / (self.1 - self.0)).clamp(0.0, 1.0)
+    }
+
+    /// Uniformly random value of the range, in steps of given size.
+    pub fn random(&self, rng: &mut Rng, step: f32) -> f32 {
+        let steps = ((self.1 - self.0) / step).round() as u32;
+        let value = self.0 + rng.u32(0..=steps) as f32 * step;
+        (value / step).round() * step
+    }
+}
+
+impl ModStats {
+    /// Modifiers which can roll on given item class.
+    pub fn for_item_class(item_class: ItemClass) -> impl Iterator<Item = &'static ModStats> {
+        MOD_STATS
+            .iter()
+            .filter(move |stats| stats.item_classes.contains(&item_class))
+    }
+
+    /// Modifier of item class by its line on the item.
+    pub fn from_line(line: &str, item_class: ItemClass) -> Option<&'static ModStats> {
+        let text = mod_text(line);
+        ModStats::for_item_class(item_class).find(|stats| stats.text == text)
+    }
+
+    /// Highest tier at item level, rolled to max values.
+    pub fn max_roll(&self, item_level: u8) -> Option<RolledMod> {
+        let tier = self.tiers_at_level(item_level).last()?;
+        Some(RolledMod {
+            mod_id: self.id.clone(),
+            stat: self.stat,
+            affix: self.affix,
+            values: tier.rolls.iter().map(|roll| roll.1).collect(),
+        })
+    }
+
+    /// Tiers available at given item level, from the lowest to the highest.
+    pub fn tiers_at_level(&self, item_level: u8) -> impl Iterator<Item = &ModTier> {
+        self.tiers
+            .iter()
+            .filter(move |tier| tier.level <= item_level)
+    }
+
+    /// Tier number, where 1 is the highest tier of the modifier.
+    pub fn tier_number(&self, index: usize) -> u8 {
+        (self.tiers.len() - index) as u8
+    }
+}
+
+/// Tier of a rolled explicit modifier and how good its roll is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModTierInfo {
+    pub mod_id: String,
+    pub stat: ModStat,
+    pub affix: Affix,
+    pub values: Vec<f32>,
+    /// 1 is the highest tier of the modifier.
+    pub tier: u8,
+    pub name: String,
+    pub rolls: Vec<RollRange>,
+    /// Highest tier which can roll at the item level.
+    pub best_tier: u8,
+    /// Roll ranges of the highest tier at the item level.
+    pub best_rolls: Vec<RollRange>,
+    /// How close the roll is to the top of its tier, from 0.0 to 1.0.
+    pub roll_quality: f32,
+}
+
+impl Explicits {
+    /// Rolled values of damage modifiers, by stat.
+    pub fn mod_values(&self) -> Vec<(ModStat, Vec<f32>)> {
+        let mut values = vec![];
+
+        if let Some(phys) = self.phys {
+            values.push((ModStat::Phys, vec![phys.0 as f32]));
+        }
+        for flat in &self.flats {
+            values.push((
+                ModStat::Flat(flat.damage_type),
+                vec![flat.range.0 as f32, flat.range.1 as f32],
+            ));
+        }
+        if let Some(atk_spd) = self.atk_spd {
+            values.push((ModStat::AttackSpeed, vec![atk_spd.0 as f32]));
+        }
+        if let Some(crit_chance) = self.crit_chance {
+            values.push((ModStat::CritChance, vec![crit_chance.0]));
+        }
+        if let Some(crit_dmg) = self.crit_dmg {
+            values.push((ModStat::CritDamage, vec![crit_dmg.0 as f32]));
+        }
+        if let Some(caster) = &self.caster {
+            if let Some(levels) = caster.spell_levels {
+                values.push((ModStat::SpellLevels, vec![levels.0 as f32]));
+            }
+            if let Some(spell_damage) = caster.spell_damage {
+                values.push((ModStat::SpellDamage, vec![spell_damage.0 as f32]));
+            }
+            for increased in &caster.increased {
+                values.push((
+                    ModStat::Increased(increased.damage_type),
+                    vec![increased.value as f32],
+                ));
+            }
+            if let Some(cast_speed) = caster.cast_speed {
+                values.push((ModStat::CastSpeed, vec![cast_speed.0 as f32]));
+            }
+        }
+
+        values
+    }
+
+    /// Tier info for every damage modifier. Values which do not fit any tier,
+    /// e.g. hybrid modifiers, are left out.
+    pub fn tiers(&self, item_class: ItemClass, item_level: u8) -> Vec<ModTierInfo> {
+        self.mod_values()
+            .into_iter()
+            .filter_map(|(stat, values)| {
+                ModStats::for_item_class(item_class)
+                    .filter(|stats| stats.stat == stat)
+                    .find_map(|stats| tier_info(stats, &values, item_level))
+            })
+            .collect()
+    }
+}
+
+/// Modifier line with numbers replaced by #.
+pub fn mod_text(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if !c.is_ascii_digit() {
+            text.push(c);
+            continue;
+        }
+
+        while chars.next_if(|c| c.is_ascii_digit() || *c == '.').is_some() {}
+        text.push('#');
+    }
+
+    text
+}
+
+fn tier_info(stats: &ModStats, values: &[f32], item_level: u8) -> Option<ModTierInfo> {
+    let (index, tier) = stats.tiers.iter().enumerate().find(|(_, tier)| {
+        tier.rolls.len() == values.len()
+            && tier
+                .rolls
+                .iter()
+                .zip(values)
+                .all(|(roll, value)| roll.contains(*value))
+    })?;
+
+    let (best_index, best) = stats
+        .tiers
+        .iter()
+        .enumerate()
+        .rfind(|(_, tier)| tier.level <= item_level)
+        .unwrap_or((index, tier));
+
+    let roll_quality = tier
+        .rolls
+        .iter()
+        .zip(values)
+        .map(|(roll, value)| roll.quality(*value))
+        .sum::<f32>()
+        / values.len() as f32;
+
+    Some(ModTierInfo {
+        mod_id: stats.id.clone(),
+        stat: stats.stat,
+        affix: stats.affix,
+        values: values.to_vec(),
+        tier: stats.tier_number(index),
+        name: tier.name.clone(),
+        rolls: tier.rolls.clone(),
+        best_tier: stats.tier_number(best_index),
+        best_rolls: best.rolls.clone(),
+        roll_quality,
+    })
+}
+
+impl Weapon {
+    pub fn item_level(&self) -> u8 {
+        self.item_level.unwrap_or(MAX_ITEM_LEVEL)
+    }
+
+    /// Tier info of explicit damage modifiers.
+    pub fn explicit_tiers(&self) -> Vec<ModTierInfo> {
+        self.explicits.tiers(self.item_class, self.item_level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttackSpeedModifier, FlatDamage, PhysModifier, Range};
+
+    #[test]
+    fn identify_tiers() {
+        let explicits = Explicits {
+            phys: Some(PhysModifier(118)),
+            flats: vec![FlatDamage {
+                damage_type: DamageType::Physical,
+                range: Range(12, 18),
+            }],
+            atk_spd: Some(AttackSpeedModifier(19)),
+            ..Default::default()
+        };
+
+        let tiers = explicits.tiers(ItemClass::Bows, 75);
+        assert_eq!(3, tiers.len());
+
+        let phys = &tiers[0];
+        assert_eq!("Bloodthirsty", phys.name);
+        assert_eq!(4, phys.tier);
+        assert_eq!(2, phys.best_tier);
+        assert!((phys.roll_quality - 8.0 / 24.0).abs() < 0.001);
+
+        let flat = &tiers[1];
+        assert_eq!("Honed", flat.name);
+        assert_eq!(Affix::Prefix, flat.affix);
+        assert!((flat.roll_quality - 0.425).abs() < 0.001);
+
+        assert_eq!(Affix::Suffix, tiers[2].affix);
+        assert_eq!(1.0, tiers[2].roll_quality);
+    }
+
+    #[test]
+    fn mod_from_line() {
+        assert_eq!(
+            "+#% to Critical Hit Chance",
+            mod_text("+1.27% to Critical Hit Chance")
+        );
+
+        let flat = ModStats::from_line("Adds 12 to 18 Physical Damage", ItemClass::Bows).unwrap();
+        assert_eq!("LocalAddedPhysicalDamageTwoHanded", flat.id);
+        assert_eq!(
+            Affix::Suffix,
+            ModStats::from_line("Grants 4 Life per Enemy Hit", ItemClass::Bows)
+                .unwrap()
+                .affix
+        );
+        assert!(ModStats::from_line("Corrupted", ItemClass::Bows).is_none());
+    }
+}
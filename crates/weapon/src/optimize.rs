@@ -0,0 +1,127 @@
+use crate::{BuildProfile, DpsWithRunes, Rune, Weapon};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What rune fills are legal for [`Weapon::optimize_runes`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuneConstraints {
+    /// Owned runes with their counts. None for unlimited runes.
+    pub inventory: Option<HashMap<Rune, u32>>,
+    /// Runes with a higher level requirement can not be socketed.
+    pub character_level: Option<u8>,
+    /// Indices of socketed runes which stay in place.
+    pub locked: Vec<usize>,
+}
+
+impl RuneConstraints {
+    pub fn allows(&self, rune: &Rune) -> bool {
+        let level = self
+            .character_level
+            .is_none_or(|level| rune.stats().level <= level);
+        let owned = self
+            .inventory
+            .as_ref()
+            .is_none_or(|inventory| inventory.get(rune).copied().unwrap_or_default() > 0);
+
+        level && owned
+    }
+
+    /// Whether the inventory has enough runes for the fill.
+    pub fn has_enough(&self, runes: &[Rune]) -> bool {
+        let Some(inventory) = &self.inventory else {
+            return true;
+        };
+
+        let mut counts: HashMap<Rune, u32> = HashMap::new();
+        for rune in runes {
+            *counts.entry(*rune).or_default() += 1;
+        }
+        counts
+            .iter()
+            .all(|(rune, count)| inventory.get(rune).copied().unwrap_or_default() >= *count)
+    }
+}
+
+impl Weapon {
+    /// Best legal rune fills, at most `top`. Locked runes are kept, other sockets
+    /// are filled with allowed runes, including partial fills.
+    pub fn optimize_runes(
+        &self,
+        constraints: &RuneConstraints,
+        profile: &BuildProfile,
+        top: usize,
+    ) -> Vec<DpsWithRunes> {
+        let locked: Vec<Rune> = self
+            .runes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| constraints.locked.contains(i))
+            .map(|(_, rune)| *rune)
+            .take(self.sockets as usize)
+            .collect();
+        let free = self.sockets as usize - locked.len();
+
+        let candidates: Vec<Rune> = match self.is_caster() {
+            true => Rune::caster_runes(),
+            false => Rune::martial_runes(),
+        }
+        .into_iter()
+        .filter(|rune| constraints.allows(rune))
+        .collect();
+
+        let mut vec: Vec<DpsWithRunes> = (0..=free)
+            .flat_map(|size| Rune::combinations(&candidates, size))
+            .filter(|runes| constraints.has_enough(runes))
+            .map(|runes| {
+                let weapon = Weapon {
+                    runes: locked.iter().copied().chain(runes).collect(),
+                    ..self.clone()
+                };
+                DpsWithRunes {
+                    dps: weapon.dps_with_profile(profile),
+                    runes: weapon.runes,
+                }
+            })
+            .collect();
+        vec.sort_by(|a, b| b.dps.score().partial_cmp(&a.dps.score()).unwrap());
+        vec.truncate(top);
+
+        vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::ironwood_shortbow;
+
+    #[test]
+    fn constrained_runes() {
+        let rune = |id: &str| Rune::from_id(id).unwrap();
+        let weapon = Weapon {
+            runes: vec![rune("LesserDesert"), rune("LesserIron")],
+            sockets: 3,
+            ..ironwood_shortbow()
+        };
+        let profile = BuildProfile::default();
+
+        let unconstrained = weapon.optimize_runes(&RuneConstraints::default(), &profile, 5);
+        assert_eq!(5, unconstrained.len());
+        assert_eq!(
+            weapon.with_different_runes()[0].runes,
+            unconstrained[0].runes
+        );
+
+        let constraints = RuneConstraints {
+            inventory: Some(HashMap::from([(rune("Iron"), 1), (rune("GreaterIron"), 5)])),
+            character_level: Some(20),
+            locked: vec![0],
+        };
+        let best = weapon.optimize_runes(&constraints, &profile, 3);
+        assert_eq!(vec![rune("LesserDesert"), rune("Iron")], best[0].runes);
+        assert!(best
+            .iter()
+            .all(|fill| fill.runes[0] == rune("LesserDesert")));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    Affix, Dps, Explicits, ItemClass, ModStat, ModStats, Quality, Rarity, RolledMod, Weapon,
+    WeaponStats,
+};
+use serde::{Deserialize, Serialize};
+
+/// Best achievable weapon of a base: highest legal tiers rolled to max,
+/// 20% quality and the best runes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerfectWeapon {
+    pub weapon: Weapon,
+    pub dps: Dps,
+}
+
+impl WeaponStats {
+    /// Perfect weapon of the base at given item level.
+    pub fn perfect(&self, item_level: u8) -> PerfectWeapon {
+        let item_class: ItemClass =
+            serde_json::from_value(serde_json::Value::String(self.item_class.clone())).unwrap();
+
+        perfect_weapon(Weapon {
+            base: self.base.clone(),
+            item_class,
+            quality: Quality(20),
+            explicits: Explicits::default(),
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: item_class.handed().default_sockets(),
+            item_level: Some(item_level),
+        })
+    }
+}
+
+impl Weapon {
+    /// Perfect weapon of the same base and item level. Keeps implicits.
+    pub fn perfect(&self) -> PerfectWeapon {
+        perfect_weapon(Weapon {
+            quality: Quality(20),
+            explicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: self.handed().default_sockets(),
+            ..self.clone()
+        })
+    }
+}
+
+fn perfect_weapon(weapon: Weapon) -> PerfectWeapon {
+    let max_rolls = |affix: Affix| -> Vec<RolledMod> {
+        ModStats::for_item_class(weapon.item_class)
+            .filter(|stats| stats.affix == affix && stats.stat != ModStat::Other)
+            .filter_map(|stats| stats.max_roll(weapon.item_level()))
+            .collect()
+    };
+    let prefixes = max_rolls(Affix::Prefix);
+    let suffixes = max_rolls(Affix::Suffix);
+    let max_affixes = Rarity::Rare.max_affixes() as usize;
+
+    let mut best: Option<PerfectWeapon> = None;
+    for prefixes in subsets(&prefixes, max_affixes.min(prefixes.len())) {
+        for suffixes in subsets(&suffixes, max_affixes.min(suffixes.len())) {
+            let mods: Vec<RolledMod> = prefixes.iter().chain(&suffixes).cloned().collect();
+            let mut candidate = weapon.clone();
+            candidate.explicits = Explicits::from_mods(&mods);
+
+            let Some(with_runes) = candidate.with_different_runes().into_iter().next() else {
+                continue;
+            };
+            candidate.runes = with_runes.runes;
+
+            if best
+                .as_ref()
+                .is_none_or(|best| with_runes.dps.score() > best.dps.score())
+            {
+                best = Some(PerfectWeapon {
+                    weapon: candidate,
+                    dps: with_runes.dps,
+                });
+            }
+        }
+    }
+
+    best.unwrap_or_else(|| PerfectWeapon {
+        dps: weapon.dps(),
+        weapon,
+    })
+}
+
+/// Subsets of given size, without repetitions.
+fn subsets<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut result = vec![];
+    for (i, item) in items.iter().enumerate() {
+        for mut rest in subsets(&items[i + 1..], size - 1) {
+            rest.insert(0, item.clone());
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DamageType, WEAPON_STATS};
+
+    #[test]
+    fn perfect_bow() {
+        let stats = WEAPON_STATS
+            .iter()
+            .find(|stats| stats.base == "Ironwood Shortbow")
+            .unwrap();
+
+        let perfect = stats.perfect(82);
+        let explicits = &perfect.weapon.explicits;
+        assert_eq!(3, explicits.flats.len() + explicits.phys.iter().count());
+        assert_eq!(28, explicits.atk_spd.unwrap().0);
+        assert_eq!(2, perfect.weapon.runes.len());
+
+        // Lower item level can not be better.
+        assert!(stats.perfect(40).dps.score() < perfect.dps.score());
+
+        assert_eq!(10, subsets(&DamageType::ALL, 2).len());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{BuildProfile, Dps, Rune, Weapon};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+/// Runes of a tier combined into one rune of the next tier.
+pub const RUNES_PER_UPGRADE: f32 = 3.0;
+/// Costs are at least this much, so free fills rank first without dividing by zero.
+pub const MIN_COST: f32 = 0.01;
+
+/// Rune prices in one currency, e.g. exalted orbs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceTable {
+    pub currency: String,
+    pub prices: HashMap<Rune, f32>,
+}
+
+#[derive(Debug)]
+pub enum PriceError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+/// Rune fill with its cost compared to the currently socketed runes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunePurchase {
+    pub runes: Vec<Rune>,
+    pub dps: Dps,
+    /// [`Dps::score`] gain over the current weapon.
+    pub dps_gain: f32,
+    /// Price of runes which are not socketed yet.
+    pub cost: f32,
+    pub dps_per_currency: f32,
+}
+
+impl PriceTable {
+    /// Prices shipped with the crate, `data/prices.json`.
+    pub fn bundled() -> PriceTable {
+        PriceTable::from_json(include_str!("../data/prices.json")).unwrap()
+    }
+
+    pub fn from_json(s: &str) -> Result<PriceTable, PriceError> {
+        serde_json::from_str(s).map_err(PriceError::Json)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<PriceTable, PriceError> {
+        let contents = std::fs::read_to_string(path).map_err(PriceError::Io)?;
+        PriceTable::from_json(&contents)
+    }
+
+    /// Cheapest way to get the rune: buy it or upgrade runes of the lower tier.
+    pub fn price(&self, rune: &Rune) -> Option<f32> {
+        let market = self.prices.get(rune).copied();
+        let upgrade = rune
+            .lower_tier()
+            .and_then(|lower| self.price(&lower))
+            .map(|price| price * RUNES_PER_UPGRADE);
+
+        match (market, upgrade) {
+            (Some(market), Some(upgrade)) => Some(market.min(upgrade)),
+            (market, upgrade) => market.or(upgrade),
+        }
+    }
+
+    /// Price of runes of the fill which are not among socketed ones.
+    pub fn cost(&self, socketed: &[Rune], fill: &[Rune]) -> Option<f32> {
+        let mut socketed = socketed.to_vec();
+        let mut cost = 0.0;
+        for rune in fill {
+            match socketed.iter().position(|s| s == rune) {
+                Some(i) => {
+                    socketed.remove(i);
+                }
+                None => cost += self.price(rune)?,
+            }
+        }
+
+        Some(cost)
+    }
+}
+
+impl Weapon {
+    /// Rune fills which increase DPS, ranked by DPS gained per currency spent.
+    /// Fills with runes missing from the price table are skipped.
+    pub fn with_different_runes_by_cost(
+        &self,
+        prices: &PriceTable,
+        profile: &BuildProfile,
+    ) -> Vec<RunePurchase> {
+        let current = self.dps_with_profile(profile).score();
+
+        let mut vec: Vec<RunePurchase> = self
+            .rune_fills()
+            .filter_map(|weapon| {
+                let cost = prices.cost(&self.runes, &weapon.runes)?;
+                let dps = weapon.dps_with_profile(profile);
+                let dps_gain = dps.score() - current;
+                (dps_gain > 0.0).then(|| RunePurchase {
+                    runes: weapon.runes,
+                    dps_per_currency: dps_gain / cost.max(MIN_COST),
+                    dps,
+                    dps_gain,
+                    cost,
+                })
+            })
+            .collect();
+        vec.sort_by(|a, b| b.dps_per_currency.partial_cmp(&a.dps_per_currency).unwrap());
+
+        vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::ironwood_shortbow;
+
+    #[test]
+    fn cheap_runes_first() {
+        let rune = |id: &str| Rune::from_id(id).unwrap();
+        let prices = PriceTable::from_json(
+            r#"{
+                "currency": "exalted",
+                "prices": { "LesserIron": 0.1, "Iron": 1.0, "GreaterIron": 10.0 }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(None, prices.price(&rune("Storm")));
+        // Iron is cheaper to upgrade from Lesser Iron runes than to buy.
+        let cost = prices
+            .cost(
+                &[rune("Iron"), rune("LesserIron")],
+                &[rune("LesserIron"), rune("Iron"), rune("Iron")],
+            )
+            .unwrap();
+        assert!((0.3 - cost).abs() < 0.0001);
+
+        let weapon = Weapon {
+            runes: vec![rune("Iron")],
+            ..ironwood_shortbow()
+        };
+
+        let ranked = weapon.with_different_runes_by_cost(&prices, &BuildProfile::default());
+        assert_eq!(vec![rune("LesserIron"), rune("Iron")], ranked[0].runes);
+        assert!(ranked.iter().all(|purchase| purchase.dps_gain > 0.0));
+        assert!(!PriceTable::bundled().prices.is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+use crate::{Condition, DamageType, Dps, DpsWithRunes, FlatDamage, Range, Rune, Weapon};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Global character modifiers, applied on top of local weapon modifiers.
+/// Every field is optional in TOML and JSON files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildProfile {
+    pub name: String,
+    /// % increased Physical Damage
+    pub increased_physical: u16,
+    /// % increased Elemental Damage, applies to fire, cold and lightning.
+    pub increased_elemental: u16,
+    pub increased_fire: u16,
+    pub increased_cold: u16,
+    pub increased_lightning: u16,
+    pub increased_chaos: u16,
+    /// % increased Attack Damage, applies to every damage type.
+    pub increased_attack: u16,
+    /// % increased Attack Speed
+    pub attack_speed: u16,
+    /// More damage multipliers, in percents. Negative for less.
+    pub more: Vec<i16>,
+    /// Damage conversions, applied in [`DamageType::CONVERSION_ORDER`]. Conversions
+    /// to an earlier type are ignored. Converting more than 100% of a type is scaled down to 100%.
+    pub conversions: Vec<Conversion>,
+    /// Damage gained as extra damage of another type, before the type is converted.
+    pub gain_as_extra: Vec<Conversion>,
+    /// Penetrates Elemental Resistances, applies to fire, cold and lightning.
+    pub elemental_penetration: u16,
+    pub fire_penetration: u16,
+    pub cold_penetration: u16,
+    pub lightning_penetration: u16,
+    /// Conditions which hold for the build, e.g. enemies are ignited.
+    /// Turn on conditional bonuses of socketed Soul Cores.
+    pub conditions: Vec<Condition>,
+}
+
+/// Part of damage of one type, converted to or gained as another type.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Conversion {
+    pub from: DamageType,
+    pub to: DamageType,
+    pub percent: u16,
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    UnsupportedExtension(String),
+}
+
+impl BuildProfile {
+    pub fn from_toml(s: &str) -> Result<BuildProfile, ProfileError> {
+        toml::from_str(s).map_err(ProfileError::Toml)
+    }
+
+    pub fn from_json(s: &str) -> Result<BuildProfile, ProfileError> {
+        serde_json::from_str(s).map_err(ProfileError::Json)
+    }
+
+    /// Loads .toml or .json profile file.
+    pub fn load(path: impl AsRef<Path>) -> Result<BuildProfile, ProfileError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(ProfileError::Io)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => BuildProfile::from_toml(&contents),
+            Some("json") => BuildProfile::from_json(&contents),
+            ext => Err(ProfileError::UnsupportedExtension(
+                ext.unwrap_or_default().to_owned(),
+            )),
+        }
+    }
+
+    /// Damage multiplier of increased and more modifiers for given damage type.
+    /// Converted damage is increased only by modifiers of the type it is converted to.
+    pub fn damage_multiplier(&self, damage_type: DamageType) -> f32 {
+        let mut increased = self.increased_attack
+            + match damage_type {
+                DamageType::Physical => self.increased_physical,
+                DamageType::Fire => self.increased_fire,
+                DamageType::Cold => self.increased_cold,
+                DamageType::Lightning => self.increased_lightning,
+                DamageType::Chaos => self.increased_chaos,
+            };
+        if damage_type.is_elemental() {
+            increased += self.increased_elemental;
+        }
+
+        let more: f32 = self
+            .more
+            .iter()
+            .map(|more| 1.0 + *more as f32 / 100.0)
+            .product();
+
+        (1.0 + increased as f32 / 100.0) * more
+    }
+
+    /// Where damage of the type ends up after conversion and gain as extra:
+    /// resulting damage types with their shares of the original damage.
+    pub fn damage_shares(&self, from: DamageType) -> Vec<(DamageType, f32)> {
+        self.damage_shares_with(from, &[])
+    }
+
+    /// [`BuildProfile::damage_shares`] with skill conversions, which are applied first.
+    /// Profile conversions of a type share what the skill leaves unconverted.
+    ///
+    /// Damage goes through [`DamageType::CONVERSION_ORDER`]: at every type, extra damage
+    /// is gained from it, then it is converted. Converted and gained damage of a later
+    /// type is converted again at its step, e.g. physical to lightning to cold.
+    pub fn damage_shares_with(
+        &self,
+        from: DamageType,
+        skill_conversions: &[Conversion],
+    ) -> Vec<(DamageType, f32)> {
+        let mut shares = [0.0; 5];
+        shares[from as usize] = 1.0;
+
+        for damage_type in DamageType::CONVERSION_ORDER {
+            let amount = shares[damage_type as usize];
+            if amount == 0.0 {
+                continue;
+            }
+
+            for gain in self
+                .gain_as_extra
+                .iter()
+                .filter(|gain| gain.from == damage_type && gain.to != damage_type)
+            {
+                shares[gain.to as usize] += amount * gain.percent as f32 / 100.0;
+            }
+
+            let of_type = |conversions: &[Conversion]| -> Vec<Conversion> {
+                conversions
+                    .iter()
+                    .filter(|conversion| {
+                        conversion.from == damage_type
+                            && conversion.to.conversion_step() > damage_type.conversion_step()
+                    })
+                    .copied()
+                    .collect()
+            };
+            let mut unconverted = 1.0;
+            for conversions in [of_type(skill_conversions), of_type(&self.conversions)] {
+                let total: f32 = conversions.iter().map(|c| c.percent as f32 / 100.0).sum();
+                let scale = match total > unconverted {
+                    true => unconverted / total,
+                    false => 1.0,
+                };
+                for conversion in &conversions {
+                    shares[conversion.to as usize] +=
+                        amount * conversion.percent as f32 / 100.0 * scale;
+                }
+                unconverted -= total * scale;
+            }
+            shares[damage_type as usize] = amount * unconverted;
+        }
+
+        DamageType::ALL
+            .into_iter()
+            .map(|damage_type| (damage_type, shares[damage_type as usize]))
+            .filter(|(_, share)| *share > 0.0)
+            .collect()
+    }
+
+    /// Enemy resistance penetrated by damage of the type.
+    pub fn penetration(&self, damage_type: DamageType) -> u16 {
+        match damage_type {
+            DamageType::Physical | DamageType::Chaos => 0,
+            DamageType::Fire => self.elemental_penetration + self.fire_penetration,
+            DamageType::Cold => self.elemental_penetration + self.cold_penetration,
+            DamageType::Lightning => self.elemental_penetration + self.lightning_penetration,
+        }
+    }
+
+    pub fn attack_speed_multiplier(&self) -> f32 {
+        1.0 + self.attack_speed as f32 / 100.0
+    }
+}
+
+impl Weapon {
+    /// Character-adjusted DPS. Local damage is converted first, then global modifiers apply.
+    /// Caster score is not affected by the profile.
+    pub fn dps_with_profile(&self, profile: &BuildProfile) -> Dps {
+        self.scaled_dps(profile, &[], |_| 1.0, 1.0)
+    }
+
+    /// [`Weapon::dps_with_profile`] with skill conversions, hit damage of every source
+    /// type scaled before conversion and hits per second scaled by `hits_multiplier`.
+    pub(crate) fn scaled_dps(
+        &self,
+        profile: &BuildProfile,
+        skill_conversions: &[Conversion],
+        hit_multiplier: impl Fn(DamageType) -> f32,
+        hits_multiplier: f32,
+    ) -> Dps {
+        let profile = &self.with_conditional_bonuses(profile);
+        let dps = self.dps();
+        let aps = profile.attack_speed_multiplier() * hits_multiplier;
+
+        let mut by_type = [0.0; 5];
+        let mut ranges = [(0.0, 0.0); 5];
+        for from in DamageType::ALL {
+            let local_dps = dps.of_type(from);
+            let local_range = self.damage_range(from);
+            if local_range.sum() == 0 {
+                continue;
+            }
+
+            let hit = hit_multiplier(from);
+            for (to, share) in profile.damage_shares_with(from, skill_conversions) {
+                let multiplier = hit * share * profile.damage_multiplier(to);
+                let i = to as usize;
+                by_type[i] += local_dps * multiplier * aps;
+                ranges[i].0 += local_range.0 as f32 * multiplier;
+                ranges[i].1 += local_range.1 as f32 * multiplier;
+            }
+        }
+
+        let [pdps, fire, cold, lightning, cdps] = by_type;
+        let edps = fire + cold + lightning;
+        let total = pdps + edps + cdps;
+
+        Dps {
+            total,
+            edps,
+            pdps,
+            cdps,
+            fire,
+            cold,
+            lightning,
+            damages: DamageType::ALL
+                .into_iter()
+                .map(|damage_type| {
+                    let (min, max) = ranges[damage_type as usize];
+                    FlatDamage {
+                        damage_type,
+                        range: Range(min.round() as u16, max.round() as u16),
+                    }
+                })
+                .filter(|flat| flat.range.sum() > 0)
+                .collect(),
+            crit_chance: dps.crit_chance,
+            total_with_crit: total * self.crit_multiplier(),
+            caster: dps.caster,
+        }
+    }
+
+    /// Profile with increased damage of socketed conditional bonuses,
+    /// whose conditions the profile turns on.
+    fn with_conditional_bonuses(&self, profile: &BuildProfile) -> BuildProfile {
+        let increased: u16 = self
+            .runes
+            .iter()
+            .filter_map(Rune::conditional)
+            .filter(|bonus| profile.conditions.contains(&bonus.condition))
+            .map(|bonus| bonus.increased_damage)
+            .sum();
+
+        BuildProfile {
+            increased_attack: profile.increased_attack + increased,
+            ..profile.clone()
+        }
+    }
+
+    /// Same as [`Weapon::with_different_runes`], ranked by character-adjusted DPS.
+    pub fn with_different_runes_with_profile(&self, profile: &BuildProfile) -> Vec<DpsWithRunes> {
+        self.ranked_rune_fills(|weapon| weapon.dps_with_profile(profile))
+    }
+
+    /// Every rune fill with its DPS, best [`Dps::score`] first.
+    pub(crate) fn ranked_rune_fills(&self, dps: impl Fn(&Weapon) -> Dps) -> Vec<DpsWithRunes> {
+        let mut vec: Vec<DpsWithRunes> = self
+            .rune_fills()
+            .map(|weapon| DpsWithRunes {
+                dps: dps(&weapon),
+                runes: weapon.runes,
+            })
+            .collect();
+        vec.sort_by(|a, b| b.dps.score().partial_cmp(&a.dps.score()).unwrap());
+
+        vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::ironwood_shortbow, DamageType, Explicits, PhysModifier, Quality, Rune};
+
+    #[test]
+    fn fire_build_prefers_fire_runes() {
+        let profile = BuildProfile::from_toml(
+            r#"
+            name = "Fire"
+            increased_fire = 300
+            more = [40]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            profile,
+            BuildProfile::from_json(r#"{ "name": "Fire", "increased_fire": 300, "more": [40] }"#)
+                .unwrap()
+        );
+
+        let weapon = Weapon {
+            explicits: Explicits {
+                phys: Some(PhysModifier(50)),
+                ..Default::default()
+            },
+            sockets: 1,
+            ..ironwood_shortbow()
+        };
+
+        assert_eq!(
+            weapon.dps().total,
+            weapon.dps_with_profile(&BuildProfile::default()).total
+        );
+        assert_eq!(
+            vec![Rune::from_id("GreaterDesert").unwrap()],
+            weapon.with_different_runes_with_profile(&profile)[0].runes
+        );
+    }
+
+    #[test]
+    fn phys_converted_to_lightning() {
+        let profile = BuildProfile::from_toml(
+            r#"
+            increased_lightning = 100
+            increased_cold = 20
+            increased_physical = 50
+
+            [[conversions]]
+            from = "physical"
+            to = "lightning"
+            percent = 100
+
+            [[gain_as_extra]]
+            from = "physical"
+            to = "cold"
+            percent = 10
+            "#,
+        )
+        .unwrap();
+
+        let weapon = Weapon {
+            quality: Quality(0),
+            explicits: Explicits::default(),
+            sockets: 1,
+            ..ironwood_shortbow()
+        };
+        let local = weapon.dps().pdps;
+
+        let dps = weapon.dps_with_profile(&profile);
+        assert_eq!(0.0, dps.pdps);
+        // Converted and gained damage only get modifiers of the type it ends up as.
+        assert!((local * 2.0 - dps.lightning).abs() < 0.01);
+        assert!((local * 0.1 * 1.2 - dps.cold).abs() < 0.01);
+        assert_eq!(
+            vec![DamageType::Cold, DamageType::Lightning],
+            dps.damages
+                .iter()
+                .map(|flat| flat.damage_type)
+                .collect::<Vec<_>>()
+        );
+
+        // Iron runes are local, they scale damage before it is converted.
+        let with_iron = Weapon {
+            runes: vec![Rune::from_id("GreaterIron").unwrap()],
+            ..weapon.clone()
+        };
+        let iron_gain = with_iron.dps().pdps / local;
+        assert!(
+            (dps.lightning * iron_gain - with_iron.dps_with_profile(&profile).lightning).abs()
+                < 0.01
+        );
+
+        // Converted lightning is converted again to cold, cold to lightning goes backwards.
+        let mut chained = profile.clone();
+        for (from, to) in [
+            (DamageType::Lightning, DamageType::Cold),
+            (DamageType::Cold, DamageType::Lightning),
+        ] {
+            chained.conversions.push(Conversion {
+                from,
+                to,
+                percent: 50,
+            });
+        }
+        assert_eq!(
+            vec![(DamageType::Cold, 0.6), (DamageType::Lightning, 0.5)],
+            chained.damage_shares(DamageType::Physical)
+        );
+        let dps = weapon.dps_with_profile(&chained);
+        assert!((local * 0.5 * 2.0 - dps.lightning).abs() < 0.01);
+        assert!((local * 0.6 * 1.2 - dps.cold).abs() < 0.01);
+    }
+}
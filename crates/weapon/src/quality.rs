@@ -0,0 +1,103 @@
+use crate::{Dps, Quality, Rarity, Weapon, MAX_CORRUPTED_QUALITY};
+use serde::{Deserialize, Serialize};
+
+/// Highest quality reachable with whetstones.
+pub const MAX_QUALITY: u8 = 20;
+
+/// DPS of the weapon raised to the quality.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityStep {
+    pub quality: Quality,
+    /// Whetstones needed from the current quality.
+    pub whetstones: u8,
+    pub dps: Dps,
+}
+
+impl Rarity {
+    /// Quality added by one Blacksmith's Whetstone.
+    pub fn quality_per_whetstone(&self) -> u8 {
+        match self {
+            Rarity::Normal => 5,
+            Rarity::Magic => 2,
+            Rarity::Rare | Rarity::Unique => 1,
+        }
+    }
+}
+
+impl Weapon {
+    /// The same weapon with given quality, up to corrupted quality cap.
+    pub fn with_quality(&self, quality: u8) -> Weapon {
+        Weapon {
+            quality: Quality(quality.min(MAX_CORRUPTED_QUALITY)),
+            ..self.clone()
+        }
+    }
+
+    pub fn dps_at_quality(&self, quality: u8) -> Dps {
+        self.with_quality(quality).dps()
+    }
+
+    /// Whetstones needed to reach the quality. None above [`MAX_QUALITY`],
+    /// which only corruption can reach.
+    pub fn whetstones_to(&self, quality: u8, rarity: Rarity) -> Option<u8> {
+        if quality > MAX_QUALITY {
+            return None;
+        }
+        let missing = quality.saturating_sub(self.quality.0);
+        Some(missing.div_ceil(rarity.quality_per_whetstone()))
+    }
+
+    /// DPS after every whetstone, from the current quality up to [`MAX_QUALITY`].
+    pub fn quality_curve(&self, rarity: Rarity) -> Vec<QualityStep> {
+        let per_whetstone = rarity.quality_per_whetstone();
+        let mut steps = vec![];
+        let mut quality = self.quality.0;
+        let mut whetstones = 0;
+
+        loop {
+            steps.push(QualityStep {
+                quality: Quality(quality),
+                whetstones,
+                dps: self.dps_at_quality(quality),
+            });
+            if quality >= MAX_QUALITY {
+                break;
+            }
+            quality = (quality + per_whetstone).min(MAX_QUALITY);
+            whetstones += 1;
+        }
+
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::ironwood_shortbow;
+
+    #[test]
+    fn quality_curve() {
+        let weapon = Weapon {
+            quality: Quality(7),
+            ..ironwood_shortbow()
+        };
+
+        assert_eq!(Some(7), weapon.whetstones_to(20, Rarity::Magic));
+        assert_eq!(Some(0), weapon.whetstones_to(5, Rarity::Rare));
+        assert_eq!(None, weapon.whetstones_to(25, Rarity::Rare));
+        assert!(weapon.dps_at_quality(25).total > weapon.dps_at_quality(20).total);
+        assert_eq!(
+            weapon.dps_at_quality(30).total,
+            weapon.dps_at_quality(40).total
+        );
+
+        let curve = weapon.quality_curve(Rarity::Normal);
+        assert_eq!(
+            vec![7, 12, 17, 20],
+            curve.iter().map(|step| step.quality.0).collect::<Vec<_>>()
+        );
+        assert_eq!(3, curve.last().unwrap().whetstones);
+        assert!(curve.windows(2).all(|w| w[0].dps.total < w[1].dps.total));
+    }
+}
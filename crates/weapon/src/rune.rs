@@ -0,0 +1,519 @@
+use crate::{
+    caster::{CastSpeedModifier, IncreasedDamage, SpellDamageModifier},
+    AttackSpeedModifier, CritChanceModifier, CritDamageModifier, DamageType,
+    ElementalDamageModifier, FlatDamage, PhysModifier,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Catalog of every socketable, embedded from `data/runes.json`.
+pub static RUNE_STATS: Lazy<Vec<RuneStats>> =
+    Lazy::new(|| serde_json::from_str(include_str!("../data/runes.json")).unwrap());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuneStats {
+    /// Stable identifier, used for (de)serialization and rune images.
+    pub id: String,
+    pub name: String,
+    pub kind: SocketableKind,
+    /// Lesser/normal/greater tier of runes. Soul Cores have no tier.
+    pub tier: Option<RuneTier>,
+    /// Id of the rune one tier lower, three of which combine into this one.
+    pub upgrades_from: Option<String>,
+    /// Level requirement of the socketable.
+    pub level: u8,
+    /// Effect when socketed into a martial weapon.
+    pub martial: Option<RuneMartialBonus>,
+    /// Effect when socketed into a caster weapon.
+    pub caster: Option<RuneCasterBonus>,
+    /// Effect when socketed into a martial weapon, which applies only under a condition.
+    pub conditional: Option<ConditionalBonus>,
+    /// Effect when socketed into armour.
+    pub armour: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketableKind {
+    Rune,
+    /// Conditional damage, e.g. against enemies with a debuff, is a [`ConditionalBonus`].
+    SoulCore,
+}
+
+/// Condition of a conditional socketable bonus. Turned on in
+/// [`BuildProfile::conditions`](crate::BuildProfile::conditions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    EnemyIgnited,
+    EnemyChilled,
+    EnemyShocked,
+    EnemyBleeding,
+    EnemyPoisoned,
+}
+
+/// X% increased Damage while the condition holds, e.g. against Ignited enemies.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalBonus {
+    pub condition: Condition,
+    pub increased_damage: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneTier {
+    Lesser,
+    Normal,
+    Greater,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneMartialBonus {
+    Phys(PhysModifier),
+    Flat(FlatDamage),
+    AttackSpeed(AttackSpeedModifier),
+    CritChance(CritChanceModifier),
+    CritDamage(CritDamageModifier),
+    /// % increased Elemental Damage with Attacks
+    IncreasedElemental(ElementalDamageModifier),
+}
+
+impl RuneMartialBonus {
+    /// Sum of two bonuses of the same stat, as displayed on the item. None for different stats.
+    pub fn try_add(&self, other: &RuneMartialBonus) -> Option<RuneMartialBonus> {
+        use RuneMartialBonus as B;
+        match (self, other) {
+            (B::Phys(a), B::Phys(b)) => Some(B::Phys(PhysModifier(a.0 + b.0))),
+            (B::Flat(a), B::Flat(b)) if a.damage_type == b.damage_type => {
+                Some(B::Flat(FlatDamage {
+                    damage_type: a.damage_type,
+                    range: a.range + b.range,
+                }))
+            }
+            (B::AttackSpeed(a), B::AttackSpeed(b)) => {
+                Some(B::AttackSpeed(AttackSpeedModifier(a.0 + b.0)))
+            }
+            (B::CritChance(a), B::CritChance(b)) => {
+                Some(B::CritChance(CritChanceModifier(a.0 + b.0)))
+            }
+            (B::CritDamage(a), B::CritDamage(b)) => {
+                Some(B::CritDamage(CritDamageModifier(a.0 + b.0)))
+            }
+            (B::IncreasedElemental(a), B::IncreasedElemental(b)) => {
+                Some(B::IncreasedElemental(ElementalDamageModifier(a.0 + b.0)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneCasterBonus {
+    SpellDamage(SpellDamageModifier),
+    Increased(IncreasedDamage),
+    CastSpeed(CastSpeedModifier),
+}
+
+impl RuneCasterBonus {
+    /// Sum of two bonuses of the same stat, as displayed on the item. None for different stats.
+    pub fn try_add(&self, other: &RuneCasterBonus) -> Option<RuneCasterBonus> {
+        use RuneCasterBonus as B;
+        match (self, other) {
+            (B::SpellDamage(a), B::SpellDamage(b)) => {
+                Some(B::SpellDamage(SpellDamageModifier(a.0 + b.0)))
+            }
+            (B::Increased(a), B::Increased(b)) if a.damage_type == b.damage_type => {
+                Some(B::Increased(IncreasedDamage {
+                    damage_type: a.damage_type,
+                    value: a.value + b.value,
+                }))
+            }
+            (B::CastSpeed(a), B::CastSpeed(b)) => Some(B::CastSpeed(CastSpeedModifier(a.0 + b.0))),
+            _ => None,
+        }
+    }
+}
+
+/// Socketed effect of a rune, as one line of item text.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketedBonus {
+    Martial(RuneMartialBonus),
+    Caster(RuneCasterBonus),
+}
+
+impl SocketedBonus {
+    pub fn try_add(&self, other: &SocketedBonus) -> Option<SocketedBonus> {
+        match (self, other) {
+            (SocketedBonus::Martial(a), SocketedBonus::Martial(b)) => {
+                a.try_add(b).map(SocketedBonus::Martial)
+            }
+            (SocketedBonus::Caster(a), SocketedBonus::Caster(b)) => {
+                a.try_add(b).map(SocketedBonus::Caster)
+            }
+            _ => None,
+        }
+    }
+
+    /// Merges bonuses of the same stat into one, like the game does for socketed runes.
+    pub fn merge(bonuses: impl IntoIterator<Item = SocketedBonus>) -> Vec<SocketedBonus> {
+        let mut merged: Vec<SocketedBonus> = vec![];
+        for bonus in bonuses {
+            match merged.iter_mut().find(|m| m.try_add(&bonus).is_some()) {
+                Some(m) => *m = m.try_add(&bonus).unwrap(),
+                None => merged.push(bonus),
+            }
+        }
+
+        merged
+    }
+
+    /// True if both contain the same bonuses, in any order.
+    pub fn same_bonuses(a: &[SocketedBonus], b: &[SocketedBonus]) -> bool {
+        a.len() == b.len() && a.iter().all(|bonus| b.contains(bonus))
+    }
+}
+
+/// Socketable from [`RUNE_STATS`] catalog. Serialized as its id, e.g. `"GreaterIron"`.
+#[derive(Clone, Copy)]
+pub struct Rune(&'static RuneStats);
+
+impl Rune {
+    /// Every socketable from the catalog.
+    pub fn runes() -> Vec<Rune> {
+        RUNE_STATS.iter().map(Rune).collect()
+    }
+
+    /// Socketables with an effect for martial weapons, conditional ones included.
+    pub fn martial_runes() -> Vec<Rune> {
+        Rune::runes()
+            .into_iter()
+            .filter(|rune| rune.martial().is_some() || rune.conditional().is_some())
+            .collect()
+    }
+
+    /// Socketables with an effect for caster weapons.
+    pub fn caster_runes() -> Vec<Rune> {
+        Rune::runes()
+            .into_iter()
+            .filter(|rune| rune.caster().is_some())
+            .collect()
+    }
+
+    /// Every combination of `size` runes from candidates, repetitions allowed, order ignored.
+    pub fn combinations(candidates: &[Rune], size: usize) -> Vec<Vec<Rune>> {
+        if size == 0 {
+            return vec![vec![]];
+        }
+
+        let mut combinations = vec![];
+        for (i, rune) in candidates.iter().enumerate() {
+            // Continue from the same rune to avoid reverse pairs like (Storm, Iron)
+            for mut rest in Rune::combinations(&candidates[i..], size - 1) {
+                rest.insert(0, *rune);
+                combinations.push(rest);
+            }
+        }
+
+        combinations
+    }
+
+    pub fn from_id(id: &str) -> Option<Rune> {
+        RUNE_STATS.iter().find(|stats| stats.id == id).map(Rune)
+    }
+
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    pub fn stats(&self) -> &'static RuneStats {
+        self.0
+    }
+
+    pub fn tier(&self) -> Option<RuneTier> {
+        self.0.tier
+    }
+
+    pub fn kind(&self) -> SocketableKind {
+        self.0.kind
+    }
+
+    /// Rune of the same kind one tier lower, e.g. Iron Rune for Greater Iron Rune.
+    pub fn lower_tier(&self) -> Option<Rune> {
+        Rune::from_id(self.stats().upgrades_from.as_deref()?)
+    }
+
+    pub fn is_iron(&self) -> bool {
+        matches!(self.martial(), Some(RuneMartialBonus::Phys(_)))
+    }
+
+    pub fn martial(&self) -> Option<RuneMartialBonus> {
+        self.0.martial
+    }
+
+    pub fn caster(&self) -> Option<RuneCasterBonus> {
+        self.0.caster
+    }
+
+    pub fn conditional(&self) -> Option<ConditionalBonus> {
+        self.0.conditional
+    }
+
+    /// Effect of the rune, socketed into the weapon of given kind.
+    pub fn socketed_bonus(&self, caster: bool) -> Option<SocketedBonus> {
+        match caster {
+            true => self.caster().map(SocketedBonus::Caster),
+            false => self.martial().map(SocketedBonus::Martial),
+        }
+    }
+
+    /// Every rune combination up to `sockets` runes, which socketed bonuses add up exactly
+    /// to given item lines. Simplest combinations come first: fewer runes, fewer distinct
+    /// runes, higher tiers.
+    pub fn decompose(socketed: &[SocketedBonus], sockets: u8, caster: bool) -> Vec<Vec<Rune>> {
+        if socketed.is_empty() {
+            return vec![];
+        }
+
+        let target = SocketedBonus::merge(socketed.iter().copied());
+        let candidates = match caster {
+            true => Rune::caster_runes(),
+            false => Rune::martial_runes(),
+        };
+
+        let mut found: Vec<Vec<Rune>> = vec![];
+        for size in 1..=sockets as usize {
+            for runes in Rune::combinations(&candidates, size) {
+                let bonuses =
+                    SocketedBonus::merge(runes.iter().filter_map(|r| r.socketed_bonus(caster)));
+                if SocketedBonus::same_bonuses(&bonuses, &target) {
+                    found.push(runes);
+                }
+            }
+        }
+
+        found.sort_by_key(|runes| {
+            let mut distinct = runes.clone();
+            distinct.dedup();
+            let tiers: u8 = runes
+                .iter()
+                .map(|rune| rune.tier().map_or(0, |tier| tier as u8))
+                .sum();
+            (runes.len(), distinct.len(), std::cmp::Reverse(tiers))
+        });
+
+        found
+    }
+
+    pub fn phys_martial(&self) -> Option<PhysModifier> {
+        match self.martial()? {
+            RuneMartialBonus::Phys(phys_modifier) => Some(phys_modifier),
+            _ => None,
+        }
+    }
+
+    pub fn flat_martial(&self) -> Option<FlatDamage> {
+        match self.martial()? {
+            RuneMartialBonus::Flat(flat) => Some(flat),
+            _ => None,
+        }
+    }
+
+    pub fn attack_speed_martial(&self) -> Option<AttackSpeedModifier> {
+        match self.martial()? {
+            RuneMartialBonus::AttackSpeed(atk_spd) => Some(atk_spd),
+            _ => None,
+        }
+    }
+
+    pub fn crit_chance_martial(&self) -> Option<CritChanceModifier> {
+        match self.martial()? {
+            RuneMartialBonus::CritChance(crit_chance) => Some(crit_chance),
+            _ => None,
+        }
+    }
+
+    pub fn crit_damage_martial(&self) -> Option<CritDamageModifier> {
+        match self.martial()? {
+            RuneMartialBonus::CritDamage(crit_dmg) => Some(crit_dmg),
+            _ => None,
+        }
+    }
+
+    pub fn elemental_martial(&self) -> Option<ElementalDamageModifier> {
+        match self.martial()? {
+            RuneMartialBonus::IncreasedElemental(elemental) => Some(elemental),
+            _ => None,
+        }
+    }
+
+    /// Runes, adding flat damage of given type, from the highest tier to the lowest.
+    pub fn flat_runes(damage_type: DamageType) -> Vec<Rune> {
+        let mut runes: Vec<Rune> = Rune::runes()
+            .into_iter()
+            .filter(|rune| {
+                rune.flat_martial()
+                    .is_some_and(|flat| flat.damage_type == damage_type)
+            })
+            .collect();
+        runes.sort_by_key(|rune| std::cmp::Reverse(rune.tier()));
+        runes
+    }
+
+    /// Runes with % increased Physical Damage, from the highest tier to the lowest.
+    pub fn phys_runes() -> Vec<Rune> {
+        let mut runes: Vec<Rune> = Rune::runes()
+            .into_iter()
+            .filter(|rune| rune.phys_martial().is_some())
+            .collect();
+        runes.sort_by_key(|rune| std::cmp::Reverse(rune.tier()));
+        runes
+    }
+}
+
+impl PartialEq for Rune {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for Rune {}
+
+impl std::hash::Hash for Rune {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl fmt::Debug for Rune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl Serialize for Rune {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rune {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Rune::from_id(&id).ok_or_else(|| serde::de::Error::custom(format!("Unknown rune: {id}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildProfile;
+
+    #[test]
+    fn catalog_roundtrip() {
+        for rune in Rune::runes() {
+            let json = serde_json::to_string(&rune).unwrap();
+            assert_eq!(rune, serde_json::from_str::<Rune>(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn soul_cores_are_martial_socketables() {
+        let ticaba = Rune::from_id("SoulCoreOfTicaba").unwrap();
+        assert_eq!(SocketableKind::SoulCore, ticaba.kind());
+        assert_eq!(Some(CritDamageModifier(12)), ticaba.crit_damage_martial());
+        assert!(Rune::martial_runes().contains(&ticaba));
+    }
+
+    #[test]
+    fn conditional_bonus_needs_profile_condition() {
+        let core = Rune(Box::leak(Box::new(RuneStats {
+            id: "ConditionalCore".to_string(),
+            name: "Conditional Soul Core".to_string(),
+            kind: SocketableKind::SoulCore,
+            tier: None,
+            upgrades_from: None,
+            level: 0,
+            martial: None,
+            caster: None,
+            conditional: Some(ConditionalBonus {
+                condition: Condition::EnemyIgnited,
+                increased_damage: 30,
+            }),
+            armour: None,
+        })));
+        let mut weapon = crate::tests::ironwood_shortbow();
+        weapon.runes = vec![core];
+
+        let unconditional = BuildProfile::default();
+        let ignited = BuildProfile {
+            conditions: vec![Condition::EnemyIgnited],
+            ..BuildProfile::default()
+        };
+        let chilled = BuildProfile {
+            conditions: vec![Condition::EnemyChilled],
+            ..BuildProfile::default()
+        };
+        let dps = |profile| weapon.dps_with_profile(profile).total;
+        assert!(dps(&ignited) > dps(&unconditional));
+        assert_eq!(dps(&chilled), dps(&unconditional));
+    }
+
+    #[test]
+    fn combinations_with_repetitions() {
+        let runes = Rune::runes();
+        let n = runes.len();
+        assert_eq!(n, Rune::combinations(&runes, 1).len());
+        assert_eq!(n * (n + 1) / 2, Rune::combinations(&runes, 2).len());
+        assert_eq!(
+            n * (n + 1) * (n + 2) / 6,
+            Rune::combinations(&runes, 3).len()
+        );
+    }
+
+    #[test]
+    fn decompose_mixed_tiers() {
+        let iron = |id| Rune::from_id(id).unwrap();
+        let phys = |value| SocketedBonus::Martial(RuneMartialBonus::Phys(PhysModifier(value)));
+
+        assert_eq!(
+            vec![vec![iron("Iron"), iron("GreaterIron")]],
+            Rune::decompose(&[phys(34)], 2, false)
+        );
+
+        // 32% is two Iron runes or Lesser Iron with Greater Iron
+        let candidates = Rune::decompose(&[phys(32)], 2, false);
+        assert_eq!(vec![iron("Iron"), iron("Iron")], candidates[0]);
+        assert_eq!(2, candidates.len());
+
+        assert!(Rune::decompose(&[phys(34)], 1, false).is_empty());
+    }
+
+    #[test]
+    fn flat_runes_ordered_by_tier() {
+        let storm = Rune::flat_runes(DamageType::Lightning);
+        assert_eq!(
+            vec!["GreaterStorm", "Storm", "LesserStorm"],
+            storm.iter().map(|r| r.id()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lower_tier_from_catalog() {
+        let rune = |id| Rune::from_id(id).unwrap();
+        assert_eq!(Some(rune("Iron")), rune("GreaterIron").lower_tier());
+        assert_eq!(Some(rune("LesserIron")), rune("Iron").lower_tier());
+        assert_eq!(None, rune("LesserIron").lower_tier());
+        assert_eq!(None, rune("SoulCoreOfTicaba").lower_tier());
+
+        for rune in Rune::runes() {
+            if let Some(lower) = rune.lower_tier() {
+                assert_eq!(rune.kind(), lower.kind());
+                assert!(lower.tier() < rune.tier());
+            }
+        }
+    }
+}
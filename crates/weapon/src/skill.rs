@@ -0,0 +1,171 @@
+use crate::{BuildProfile, Conversion, DamageType, Dps, DpsWithRunes, ItemClass, Weapon};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+pub static SKILL_STATS: Lazy<Vec<SkillStats>> =
+    Lazy::new(|| serde_json::from_str(include_str!("../data/skills.json")).unwrap());
+
+/// Attack skill, as in skills.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillStats {
+    pub id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Weapons the skill can be used with. Empty for any weapon.
+    pub item_classes: Vec<ItemClass>,
+    /// Hit damage in percents of weapon damage.
+    pub damage_effectiveness: u16,
+    /// Effectiveness of added damage from modifiers and runes, in percents.
+    pub added_damage_multiplier: u16,
+    /// Attack speed in percents of weapon attack speed.
+    pub attack_speed_multiplier: u16,
+    /// Hits on a single target per use, e.g. projectiles.
+    pub hits_per_use: f32,
+    /// Skill conversions, applied before the build profile ones.
+    pub conversions: Vec<Conversion>,
+}
+
+impl SkillStats {
+    pub fn from_id(id: &str) -> Option<&'static SkillStats> {
+        SKILL_STATS.iter().find(|stats| stats.id == id)
+    }
+
+    pub fn for_item_class(item_class: ItemClass) -> impl Iterator<Item = &'static SkillStats> {
+        SKILL_STATS
+            .iter()
+            .filter(move |stats| stats.can_use(item_class))
+    }
+
+    pub fn can_use(&self, item_class: ItemClass) -> bool {
+        self.item_classes.is_empty() || self.item_classes.contains(&item_class)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Hit damage multiplier of the weapon damage type. Base damage of the weapon
+    /// is scaled by damage effectiveness, added damage also by added damage multiplier.
+    pub fn hit_multiplier(&self, weapon: &Weapon, damage_type: DamageType) -> f32 {
+        let effectiveness = self.damage_effectiveness as f32 / 100.0;
+        let flat = weapon.flat_damage(damage_type).sum() as f32;
+        if flat == 0.0 {
+            return effectiveness;
+        }
+
+        let base = weapon
+            .base_damage()
+            .iter()
+            .filter(|base| base.damage_type == damage_type)
+            .map(|base| base.range.sum() as f32)
+            .sum::<f32>();
+        let added = flat - base;
+
+        effectiveness * (base + added * self.added_damage_multiplier as f32 / 100.0) / flat
+    }
+
+    /// Hits per second multiplier over weapon attacks per second.
+    pub fn hits_multiplier(&self) -> f32 {
+        self.attack_speed_multiplier as f32 / 100.0 * self.hits_per_use
+    }
+}
+
+impl Weapon {
+    /// DPS of the skill used with the weapon and build profile.
+    /// None, if the skill cannot be used with the weapon.
+    pub fn skill_dps(&self, skill: &SkillStats, profile: &BuildProfile) -> Option<Dps> {
+        skill
+            .can_use(self.item_class)
+            .then(|| self.usable_skill_dps(skill, profile))
+    }
+
+    /// Same as [`Weapon::with_different_runes_with_profile`], ranked by skill DPS.
+    /// None, if the skill cannot be used with the weapon.
+    pub fn with_different_runes_for_skill(
+        &self,
+        skill: &SkillStats,
+        profile: &BuildProfile,
+    ) -> Option<Vec<DpsWithRunes>> {
+        skill
+            .can_use(self.item_class)
+            .then(|| self.ranked_rune_fills(|weapon| weapon.usable_skill_dps(skill, profile)))
+    }
+
+    fn usable_skill_dps(&self, skill: &SkillStats, profile: &BuildProfile) -> Dps {
+        self.scaled_dps(
+            profile,
+            &skill.conversions,
+            |damage_type| skill.hit_multiplier(self, damage_type),
+            skill.hits_multiplier(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::ironwood_shortbow, Explicits, FlatDamage, PhysModifier, Range, Rune};
+
+    #[test]
+    fn skill_dps() {
+        let weapon = Weapon {
+            explicits: Explicits {
+                phys: Some(PhysModifier(50)),
+                flats: vec![FlatDamage {
+                    damage_type: DamageType::Physical,
+                    range: Range(10, 20),
+                }],
+                ..Default::default()
+            },
+            sockets: 1,
+            ..ironwood_shortbow()
+        };
+        let profile = BuildProfile::default();
+
+        let attack = SkillStats::from_id("Attack").unwrap();
+        let attack_dps = weapon.skill_dps(attack, &profile).unwrap();
+        assert!((weapon.dps().total - attack_dps.total).abs() < 0.01);
+
+        // Added damage is as effective as base damage, so the whole hit scales.
+        let rain = SkillStats::from_id("RainOfArrows").unwrap();
+        assert_eq!(100, rain.added_damage_multiplier);
+        let expected =
+            weapon.dps().total * rain.damage_effectiveness as f32 / 100.0 * rain.hits_multiplier();
+        let actual = weapon.skill_dps(rain, &profile).unwrap().total;
+        assert!((expected - actual).abs() < 0.01);
+
+        let crossbow = Weapon {
+            item_class: ItemClass::Crossbows,
+            ..weapon.clone()
+        };
+        assert!(crossbow.skill_dps(rain, &profile).is_none());
+        assert!(crossbow
+            .with_different_runes_for_skill(rain, &profile)
+            .is_none());
+
+        // Half of physical damage is converted, lightning runes are added on top.
+        let lightning_arrow = SkillStats::from_id("LightningArrow").unwrap();
+        let dps = weapon.skill_dps(lightning_arrow, &profile).unwrap();
+        assert!((dps.pdps - dps.lightning).abs() < 0.01);
+        // The skill converts first, profile conversion gets the unconverted half.
+        let fire = BuildProfile {
+            conversions: vec![Conversion {
+                from: DamageType::Physical,
+                to: DamageType::Fire,
+                percent: 100,
+            }],
+            ..Default::default()
+        };
+        let converted = weapon.skill_dps(lightning_arrow, &fire).unwrap();
+        assert_eq!(0.0, converted.pdps);
+        assert!((dps.lightning - converted.lightning).abs() < 0.01);
+        assert!((dps.pdps - converted.fire).abs() < 0.01);
+        assert_eq!(
+            vec![Rune::from_id("GreaterIron").unwrap()],
+            weapon
+                .with_different_runes_for_skill(lightning_arrow, &profile)
+                .unwrap()[0]
+                .runes
+        );
+    }
+}
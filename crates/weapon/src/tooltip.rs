@@ -0,0 +1,354 @@
+use crate::{
+    caster::CasterExplicits, Affix, DamageType, Explicits, FlatDamage, Range, Rarity,
+    RuneCasterBonus, RuneMartialBonus, SocketedBonus, Weapon,
+};
+use serde::{Deserialize, Serialize};
+
+/// Separator of item text sections.
+pub const SECTION_SEPARATOR: &str = "--------";
+/// Name line of rare and unique items, when no name is given to [`Weapon::item_text`].
+pub const PLACEHOLDER_ITEM_NAME: &str = "Unnamed Item";
+
+/// Weapon properties as the game displays them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tooltip {
+    pub quality: u8,
+    /// Hit damage of every damage type, rounded.
+    pub damages: Vec<FlatDamage>,
+    pub crit_chance: f32,
+    pub attacks_per_second: f32,
+    /// Displayed lines, e.g. "Physical Damage: 144-194 (augmented)".
+    pub lines: Vec<String>,
+}
+
+impl Weapon {
+    /// Properties section of the item tooltip.
+    pub fn tooltip(&self) -> Tooltip {
+        let base = self.base_stats();
+        let damages = self.damage_ranges();
+        let attacks_per_second = self.attacks_per_second();
+        let crit_chance = self.crit_chance();
+        let augmented = |changed: bool| match changed {
+            true => " (augmented)",
+            false => "",
+        };
+        let base_range = |damage_type: DamageType| -> Range {
+            base.damages
+                .iter()
+                .filter(|flat| flat.damage_type == damage_type)
+                .map(|flat| flat.range)
+                .sum()
+        };
+        // Physical damage is marked as augmented, other types are tagged by type.
+        let range = |flat: &FlatDamage| match flat.damage_type {
+            DamageType::Physical => format!(
+                "{}-{}{}",
+                flat.range.0,
+                flat.range.1,
+                augmented(flat.range != base_range(flat.damage_type))
+            ),
+            damage_type => format!(
+                "{}-{} ({})",
+                flat.range.0,
+                flat.range.1,
+                damage_type.name().to_lowercase()
+            ),
+        };
+
+        let mut lines = vec![];
+        if self.quality.0 > 0 {
+            lines.push(format!("Quality: +{}% (augmented)", self.quality.0));
+        }
+        let line =
+            |flat: &FlatDamage| format!("{} Damage: {}", flat.damage_type.name(), range(flat));
+        let of_type =
+            |damage_type: DamageType| damages.iter().find(|f| f.damage_type == damage_type);
+
+        if let Some(phys) = of_type(DamageType::Physical) {
+            lines.push(line(phys));
+        }
+        // Several elements share one line.
+        let elemental: Vec<&FlatDamage> = damages.iter().filter(|f| f.is_elemental()).collect();
+        match elemental.as_slice() {
+            [] => {}
+            [flat] => lines.push(line(flat)),
+            flats => lines.push(format!(
+                "Elemental Damage: {}",
+                flats
+                    .iter()
+                    .map(|flat| range(flat))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+        if let Some(chaos) = of_type(DamageType::Chaos) {
+            lines.push(line(chaos));
+        }
+        // Caster weapons have no attack properties.
+        if !self.is_caster() {
+            lines.push(format!(
+                "Critical Hit Chance: {crit_chance:.2}%{}",
+                augmented(crit_chance != base.crit)
+            ));
+            lines.push(format!(
+                "Attacks per Second: {attacks_per_second:.2}{}",
+                augmented(attacks_per_second != base.aps)
+            ));
+        }
+
+        Tooltip {
+            quality: self.quality.0,
+            damages,
+            crit_chance,
+            attacks_per_second,
+            lines,
+        }
+    }
+
+    /// Item text, as the game copies it to the clipboard. The parser reads it back.
+    /// Rare and unique items get the name line before the base, [`PLACEHOLDER_ITEM_NAME`]
+    /// without a name. Magic items are named by their explicit tiers, as the game does.
+    pub fn item_text(&self, rarity: Rarity, name: Option<&str>) -> String {
+        let rarity_value = serde_json::to_value(rarity).unwrap();
+        let rarity_name = rarity_value.as_str().unwrap_or_default();
+        let item_class = serde_json::to_value(self.item_class).unwrap();
+
+        let mut header = vec![
+            format!("Item Class: {}", item_class.as_str().unwrap_or_default()),
+            format!(
+                "Rarity: {}{}",
+                rarity_name[..1].to_uppercase(),
+                &rarity_name[1..]
+            ),
+        ];
+        match rarity {
+            Rarity::Normal => header.push(self.base.clone()),
+            Rarity::Magic => header.push(self.magic_name()),
+            Rarity::Rare | Rarity::Unique => {
+                header.push(name.unwrap_or(PLACEHOLDER_ITEM_NAME).to_owned());
+                header.push(self.base.clone());
+            }
+        }
+
+        let mut sections = vec![header, self.tooltip().lines];
+        if self.sockets > 0 {
+            sections.push(vec![format!(
+                "Sockets: {}",
+                vec!["S"; self.sockets as usize].join(" ")
+            )]);
+        }
+        if let Some(item_level) = self.item_level {
+            sections.push(vec![format!("Item Level: {item_level}")]);
+        }
+
+        let socketed = SocketedBonus::merge(
+            self.runes
+                .iter()
+                .filter_map(|rune| rune.socketed_bonus(self.is_caster())),
+        );
+        for (lines, suffix) in [
+            (self.enchants.lines(), " (enchant)"),
+            (
+                socketed.iter().map(SocketedBonus::line).collect(),
+                " (rune)",
+            ),
+            (self.implicits.lines(), " (implicit)"),
+            (self.explicits.lines(), ""),
+        ] {
+            if !lines.is_empty() {
+                sections.push(lines.into_iter().map(|line| line + suffix).collect());
+            }
+        }
+
+        sections
+            .into_iter()
+            .map(|section| section.join("\n"))
+            .collect::<Vec<_>>()
+            .join(&format!("\n{SECTION_SEPARATOR}\n"))
+    }
+
+    /// Base with names of the explicit prefix and suffix tiers, e.g. "Heavy Warpick of Skill".
+    fn magic_name(&self) -> String {
+        let tiers = self.explicit_tiers();
+        let name = |affix: Affix| {
+            tiers
+                .iter()
+                .find(|info| info.affix == affix)
+                .map(|info| info.name.clone())
+        };
+
+        [
+            name(Affix::Prefix),
+            Some(self.base.clone()),
+            name(Affix::Suffix),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+impl Explicits {
+    /// Modifier lines as displayed on the item. Attack speed goes last.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if let Some(phys) = self.phys {
+            lines.push(format!("{}% increased Physical Damage", phys.0));
+        }
+        for flat in &self.flats {
+            lines.push(flat_line(flat));
+        }
+        if let Some(crit_chance) = self.crit_chance {
+            lines.push(format!("+{}% to Critical Hit Chance", crit_chance.0));
+        }
+        if let Some(crit_dmg) = self.crit_dmg {
+            lines.push(format!("+{}% to Critical Damage Bonus", crit_dmg.0));
+        }
+        if let Some(caster) = &self.caster {
+            lines.extend(caster.lines());
+        }
+        if let Some(atk_spd) = self.atk_spd {
+            lines.push(format!("{}% increased Attack Speed", atk_spd.0));
+        }
+
+        lines
+    }
+}
+
+impl CasterExplicits {
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if let Some(levels) = self.spell_levels {
+            lines.push(format!("+{} to Level of all Spell Skills", levels.0));
+        }
+        if let Some(spell_damage) = self.spell_damage {
+            lines.push(format!("{}% increased Spell Damage", spell_damage.0));
+        }
+        for increased in &self.increased {
+            lines.push(format!(
+                "{}% increased {} Damage",
+                increased.value,
+                increased.damage_type.name()
+            ));
+        }
+        if let Some(cast_speed) = self.cast_speed {
+            lines.push(format!("{}% increased Cast Speed", cast_speed.0));
+        }
+
+        lines
+    }
+}
+
+impl SocketedBonus {
+    /// Line of the bonus, without "(rune)".
+    pub fn line(&self) -> String {
+        match self {
+            SocketedBonus::Martial(bonus) => match bonus {
+                RuneMartialBonus::Phys(phys) => format!("{}% increased Physical Damage", phys.0),
+                RuneMartialBonus::Flat(flat) => flat_line(flat),
+                RuneMartialBonus::AttackSpeed(atk_spd) => {
+                    format!("{}% increased Attack Speed", atk_spd.0)
+                }
+                RuneMartialBonus::CritChance(crit_chance) => {
+                    format!("+{}% to Critical Hit Chance", crit_chance.0)
+                }
+                RuneMartialBonus::CritDamage(crit_dmg) => {
+                    format!("+{}% to Critical Damage Bonus", crit_dmg.0)
+                }
+                RuneMartialBonus::IncreasedElemental(elemental) => {
+                    format!("{}% increased Elemental Damage with Attacks", elemental.0)
+                }
+            },
+            SocketedBonus::Caster(bonus) => match bonus {
+                RuneCasterBonus::SpellDamage(spell_damage) => {
+                    format!("{}% increased Spell Damage", spell_damage.0)
+                }
+                RuneCasterBonus::Increased(increased) => format!(
+                    "{}% increased {} Damage",
+                    increased.value,
+                    increased.damage_type.name()
+                ),
+                RuneCasterBonus::CastSpeed(cast_speed) => {
+                    format!("{}% increased Cast Speed", cast_speed.0)
+                }
+            },
+        }
+    }
+}
+
+fn flat_line(flat: &FlatDamage) -> String {
+    format!(
+        "Adds {} to {} {} Damage",
+        flat.range.0,
+        flat.range.1,
+        flat.damage_type.name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttackSpeedModifier, CritDamageModifier, ItemClass, PhysModifier, Quality, Rune};
+
+    #[test]
+    fn tooltip_lines() {
+        // Plague Crusher from the parser fixtures. The fixture was copied before
+        // the game tagged elemental damage by type.
+        let weapon = Weapon {
+            base: "Leaden Greathammer".to_owned(),
+            item_class: ItemClass::TwoHandMaces,
+            quality: Quality(20),
+            explicits: Explicits {
+                phys: Some(PhysModifier(107)),
+                flats: vec![FlatDamage {
+                    damage_type: DamageType::Fire,
+                    range: Range(7, 16),
+                }],
+                crit_dmg: Some(CritDamageModifier(16)),
+                atk_spd: Some(AttackSpeedModifier(9)),
+                ..Default::default()
+            },
+            implicits: Explicits::default(),
+            enchants: Explicits::default(),
+            runes: vec![],
+            sockets: 2,
+            item_level: Some(33),
+        };
+
+        assert_eq!(
+            vec![
+                "Quality: +20% (augmented)",
+                "Physical Damage: 144-194 (augmented)",
+                "Fire Damage: 7-16 (fire)",
+                "Critical Hit Chance: 5.00%",
+                "Attacks per Second: 1.20 (augmented)",
+            ],
+            weapon.tooltip().lines
+        );
+
+        let magic = Weapon {
+            explicits: Explicits {
+                phys: Some(PhysModifier(107)),
+                atk_spd: Some(AttackSpeedModifier(9)),
+                ..Default::default()
+            },
+            ..weapon.clone()
+        }
+        .item_text(Rarity::Magic, None);
+        assert!(magic.contains("Rarity: Magic\nVicious Leaden Greathammer of Ease\n--------"));
+
+        let iron = Rune::from_id("Iron").unwrap();
+        let text = Weapon {
+            runes: vec![iron, iron],
+            ..weapon
+        }
+        .item_text(Rarity::Rare, Some("Doom Bane"));
+        assert!(text.starts_with(
+            "Item Class: Two Hand Maces\nRarity: Rare\nDoom Bane\nLeaden Greathammer\n--------"
+        ));
+        assert!(text.contains("Sockets: S S\n--------\nItem Level: 33"));
+        assert!(text.contains("\n32% increased Physical Damage (rune)\n"));
+        assert!(text.ends_with("+16% to Critical Damage Bonus\n9% increased Attack Speed"));
+    }
+}
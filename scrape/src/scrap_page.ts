@@ -7,7 +7,22 @@ import {
 	type ItemClass,
 } from './weapon.ts';
 
-export const WIKI_WEAPON_TYPES = ['maces', 'quarterstaves', 'bows', 'crossbows', 'spears', 'talismans'] as const;
+export const WIKI_WEAPON_TYPES = [
+	'maces',
+	'quarterstaves',
+	'bows',
+	'crossbows',
+	'spears',
+	'talismans',
+	'swords',
+	'axes',
+	'daggers',
+	'claws',
+	'flails',
+	'wands',
+	'staves',
+	'sceptres',
+] as const;
 export type WikiWeaponType = (typeof WIKI_WEAPON_TYPES)[number];
 
 function page_url(weapon_type: WikiWeaponType): string {
@@ -86,10 +101,30 @@ export async function scrap_page(weapon_type: WikiWeaponType, page: Page): Promi
 				});
 			}
 
-			function column_indexes(table: HTMLTableElement): { damage: number; attacks_per_second: number } {
+			/** Implicit modifier lines, one per line break. Wiki uses en dashes in ranges. */
+			function parse_implicits_td(td: HTMLTableCellElement | undefined): Array<string> {
+				if (!td) {
+					return [];
+				}
+
+				return td.innerText
+					.split('\n')
+					.map(line => line.trim().replaceAll('–', '-'))
+					.filter(line => line.length > 0);
+			}
+
+			function column_indexes(table: HTMLTableElement): {
+				damage: number;
+				attacks_per_second: number;
+				crit: number;
+				implicits: number;
+			} {
+				// Caster weapons tables have no damage columns
 				let column_index = 0;
-				let damage = 0;
-				let attacks_per_second = 0;
+				let damage = -1;
+				let attacks_per_second = -1;
+				let crit = -1;
+				let implicits = -1;
 
 				const thead = table.querySelector('thead');
 				if (!thead) {
@@ -104,11 +139,17 @@ export async function scrap_page(weapon_type: WikiWeaponType, page: Page): Promi
 					if (th.textContent?.trim() === 'APS') {
 						attacks_per_second = column_index;
 					}
+					if (th.textContent?.trim() === 'Crit') {
+						crit = column_index;
+					}
+					if (th.textContent?.trim() === 'Stats') {
+						implicits = column_index;
+					}
 
 					column_index += 1;
 				}
 
-				return { damage, attacks_per_second };
+				return { damage, attacks_per_second, crit, implicits };
 			}
 
 			return (
@@ -120,15 +161,19 @@ export async function scrap_page(weapon_type: WikiWeaponType, page: Page): Promi
 						return Array.from(table.querySelector('tbody')!.querySelectorAll('tr')).map(tr => {
 							const cells = tr.querySelectorAll('td');
 							const { title, img } = parse_first_td(cells[0]!);
-							const aps = Number(cells[indexes.attacks_per_second]!.textContent);
-							const damages = parse_damage_td(cells[indexes.damage]!);
+							const aps = Number(cells[indexes.attacks_per_second]?.textContent ?? 0);
+							const crit = Number(cells[indexes.crit]?.textContent?.trim().replace('%', '') ?? 0);
+							const damages = indexes.damage === -1 ? [] : parse_damage_td(cells[indexes.damage]!);
+							const implicits = indexes.implicits === -1 ? [] : parse_implicits_td(cells[indexes.implicits]);
 
 							return {
 								base: title,
 								item_class: get_item_class(table, ctx),
 								img: img,
 								aps,
+								crit,
 								damages,
+								implicits,
 							};
 						});
 					})
@@ -9,6 +9,10 @@ export type WeaponBaseStats = {
 	img: string;
 	damages: Array<FlatDamage>;
 	aps: number;
+	/** Base critical hit chance, in percents. */
+	crit: number;
+	/** Base implicit modifiers, as displayed on the item. */
+	implicits: Array<string>;
 };
 
 export const SUPPORTED_ITEM_CLASSES = [
@@ -19,5 +23,15 @@ export const SUPPORTED_ITEM_CLASSES = [
 	'Crossbows',
 	'Spears',
 	'Talismans',
+	'One Hand Swords',
+	'Two Hand Swords',
+	'One Hand Axes',
+	'Two Hand Axes',
+	'Daggers',
+	'Claws',
+	'Flails',
+	'Wands',
+	'Staves',
+	'Sceptres',
 ] as const;
 export type ItemClass = (typeof SUPPORTED_ITEM_CLASSES)[number];